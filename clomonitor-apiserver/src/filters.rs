@@ -1,14 +1,16 @@
-/// Template filter that returns the rating letter corresponding to the score
-/// value provided.
-pub fn rating(score: &f64) -> ::askama::Result<char> {
-    Ok(clomonitor_core::score::rating(*score))
+use clomonitor_core::score::Score;
+
+/// Template filter that returns the rating letter corresponding to the value
+/// provided, using the rating thresholds of the score given.
+pub fn rating(value: &f64, score: &Score) -> ::askama::Result<char> {
+    Ok(score.rating_for(*value))
 }
 
-/// Template filter that returns the rating letter corresponding to the score
-/// value provided.
-pub fn rating_opt(score: &Option<f64>) -> ::askama::Result<String> {
-    Ok(match score {
-        Some(v) => clomonitor_core::score::rating(*v).to_string(),
+/// Template filter that returns the rating letter corresponding to the value
+/// provided, using the rating thresholds of the score given.
+pub fn rating_opt(value: &Option<f64>, score: &Score) -> ::askama::Result<String> {
+    Ok(match value {
+        Some(v) => score.rating_for(*v).to_string(),
         None => "na".to_string(),
     })
 }
@@ -21,17 +23,7 @@ pub fn round(v: &f64) -> ::askama::Result<usize> {
 
 /// Template filter that returns the width of the section score bar.
 pub fn rs_section_score_width(score: &Option<f64>) -> ::askama::Result<f64> {
-    Ok(match score {
-        Some(v) => {
-            let width = (v * 1.06).round();
-            if width < 2.0 {
-                2.0
-            } else {
-                width
-            }
-        }
-        None => 0.0,
-    })
+    Ok(clomonitor_core::score::section_bar_width(*score))
 }
 
 /// Template filter that returns the integer part of the rounded score value
@@ -6,7 +6,7 @@ use axum::{
     http::StatusCode,
     response::{self, Headers, IntoResponse},
 };
-use clomonitor_core::score::Score;
+use clomonitor_core::score::{self, Explanation, Score};
 use deadpool_postgres::Pool;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -108,6 +108,88 @@ pub(crate) async fn project(
     }
 }
 
+/// Handler that returns the explanation of the project's score, including the
+/// fixes needed to reach the next rating.
+pub(crate) async fn score_explanation(
+    Extension(db_pool): Extension<Pool>,
+    extract::Path((org, project)): extract::Path<(String, String)>,
+) -> Result<response::Json<Explanation>, StatusCode> {
+    // Get project's repositories scores explanations from database
+    let db = db_pool.get().await.map_err(internal_error)?;
+    let rows = db
+        .query(
+            "
+            select r.name, r.score_explanation
+            from repository r
+            join project p using (project_id)
+            join organization o using (organization_id)
+            where o.name = $1::text
+            and p.name = $2::text
+            and r.score_explanation is not null
+            order by r.name asc
+            ",
+            &[&org, &project],
+        )
+        .await
+        .map_err(internal_error)?;
+    if rows.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let explanations = rows
+        .iter()
+        .map(|row| {
+            let Json(explanation): Json<Explanation> = row.get("score_explanation");
+            (row.get("name"), explanation)
+        })
+        .collect();
+
+    Ok(response::Json(score::merge_explanations(explanations)))
+}
+
+/// Handler that returns the project's score time series.
+pub(crate) async fn project_score_series(
+    Extension(db_pool): Extension<Pool>,
+    extract::Path((org, project)): extract::Path<(String, String)>,
+) -> Result<response::Json<Value>, StatusCode> {
+    // Get project's score snapshots from database
+    let db = db_pool.get().await.map_err(internal_error)?;
+    let row = db
+        .query_one(
+            "select get_project_score_series($1::text, $2::text)",
+            &[&org, &project],
+        )
+        .await
+        .map_err(internal_error)?;
+    let series: Option<Json<Value>> = row.get(0);
+
+    match series {
+        Some(Json(series)) => Ok(response::Json(series)),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Handler that returns the repository's score time series.
+pub(crate) async fn repository_score_series(
+    Extension(db_pool): Extension<Pool>,
+    extract::Path((org, project, repository)): extract::Path<(String, String, String)>,
+) -> Result<response::Json<Value>, StatusCode> {
+    // Get repository's score snapshots from database
+    let db = db_pool.get().await.map_err(internal_error)?;
+    let row = db
+        .query_one(
+            "select get_repository_score_series($1::text, $2::text, $3::text)",
+            &[&org, &project, &repository],
+        )
+        .await
+        .map_err(internal_error)?;
+    let series: Option<Json<Value>> = row.get(0);
+
+    match series {
+        Some(Json(series)) => Ok(response::Json(series)),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
 /// Template for the report summary SVG image.
 #[derive(Template)]
 #[template(path = "report-summary.svg")]
@@ -40,6 +40,18 @@ pub(crate) fn setup(cfg: &Config, db_pool: Pool) -> Result<Router, Error> {
             "/api/projects/:org/:project/report-summary",
             get(report_summary_svg),
         )
+        .route(
+            "/api/projects/:org/:project/score-explanation",
+            get(score_explanation),
+        )
+        .route(
+            "/api/projects/:org/:project/score-series",
+            get(project_score_series),
+        )
+        .route(
+            "/api/projects/:org/:project/repositories/:repository/score-series",
+            get(repository_score_series),
+        )
         .route(
             "/",
             get_service(ServeFile::new(&index_path)).handle_error(error_handler),
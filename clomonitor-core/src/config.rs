@@ -121,3 +121,85 @@ lazy_static! {
         m
     };
 }
+
+// Checks documentation
+pub const CHECKS_DOCS_URL: &str = "https://github.com/cncf/clomonitor/blob/main/docs/checks.md";
+
+lazy_static! {
+    pub static ref CHECK_DOCS_ANCHOR: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+
+        // Documentation
+        m.insert(ADOPTERS, "adopters");
+        m.insert(CHANGELOG, "changelog");
+        m.insert(CODE_OF_CONDUCT, "code-of-conduct");
+        m.insert(CONTRIBUTING, "contributing");
+        m.insert(GOVERNANCE, "governance");
+        m.insert(MAINTAINERS, "maintainers");
+        m.insert(README, "readme");
+        m.insert(ROADMAP, "roadmap");
+        m.insert(WEBSITE, "website");
+
+        // License
+        m.insert(LICENSE_APPROVED, "approved-license");
+        m.insert(LICENSE_SCANNING, "license-scanning");
+        m.insert(LICENSE_SPDX, "spdx-id");
+
+        // Best practices
+        m.insert(ARTIFACTHUB_BADGE, "artifact-hub-badge");
+        m.insert(COMMUNITY_MEETING, "community-meeting");
+        m.insert(DCO, "developer-certificate-of-origin");
+        m.insert(OPENSSF_BADGE, "openssf-badge");
+        m.insert(RECENT_RELEASE, "recent-release");
+        m.insert(SLACK_PRESENCE, "slack-presence");
+
+        // Security
+        m.insert(SBOM, "software-bill-of-materials-sbom");
+        m.insert(SECURITY_POLICY, "security-policy");
+
+        // Legal
+        m.insert(TRADEMARK_DISCLAIMER, "trademark-disclaimer");
+
+        m
+    };
+}
+
+// Checks remediation hints
+lazy_static! {
+    pub static ref CHECK_REMEDIATION: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+
+        // Documentation
+        m.insert(ADOPTERS, "Add an ADOPTERS file to the repository or an adopters section to the README file");
+        m.insert(CHANGELOG, "Add a CHANGELOG file to the repository, a changelog section to the README file or a reference to the changelog in the release notes");
+        m.insert(CODE_OF_CONDUCT, "Add a CODE_OF_CONDUCT file to the repository (or to the organization's .github repository) or a code of conduct section to the README file");
+        m.insert(CONTRIBUTING, "Add a CONTRIBUTING file to the repository (or to the organization's .github repository) or a contributing section to the README file");
+        m.insert(GOVERNANCE, "Add a GOVERNANCE file to the repository or a governance section to the README file");
+        m.insert(MAINTAINERS, "Add a MAINTAINERS, OWNERS or CODEOWNERS file to the repository or a maintainers section to the README file");
+        m.insert(README, "Add a README file to the repository");
+        m.insert(ROADMAP, "Add a ROADMAP file to the repository or a roadmap section to the README file");
+        m.insert(WEBSITE, "Set the project's website url in the repository settings");
+
+        // License
+        m.insert(LICENSE_APPROVED, "Use one of the licenses approved by the CNCF");
+        m.insert(LICENSE_SCANNING, "Set the license scanning url in the .clomonitor.yml metadata file or add a FOSSA or Snyk badge to the README file");
+        m.insert(LICENSE_SPDX, "Add a LICENSE file with the full text of the license to the repository");
+
+        // Best practices
+        m.insert(ARTIFACTHUB_BADGE, "Add the Artifact Hub badge to the README file");
+        m.insert(COMMUNITY_MEETING, "Add a reference to the community meetings to the README file");
+        m.insert(DCO, "Require the DCO signature in commits (i.e. using the DCO GitHub app)");
+        m.insert(OPENSSF_BADGE, "Get an OpenSSF (CII) best practices badge and add it to the README file");
+        m.insert(RECENT_RELEASE, "Publish a new release (the last one must have been published within the last year)");
+        m.insert(SLACK_PRESENCE, "Add a reference to the project's Slack channel to the README file");
+
+        // Security
+        m.insert(SBOM, "Publish the SBOM as an asset of the releases or add a SBOM section to the README file");
+        m.insert(SECURITY_POLICY, "Add a SECURITY file to the repository (or to the organization's .github repository) or a security section to the README file");
+
+        // Legal
+        m.insert(TRADEMARK_DISCLAIMER, "Add the Linux Foundation trademark disclaimer to the footer of the project's website");
+
+        m
+    };
+}
@@ -0,0 +1,565 @@
+use git2::{
+    build::CheckoutBuilder, AutotagOption, Cred, CredentialType, Direction, ErrorClass, ErrorCode,
+    FetchOptions, Oid, Remote, RemoteCallbacks, Repository,
+};
+use reqwest::Url;
+use std::{fmt, fs, io, path::Path, sync::Arc};
+use tokio::task;
+
+/// Reference where the remote HEAD is stored when fetching into a mirror.
+const MIRROR_HEAD_REF: &str = "refs/clomonitor/head";
+
+/// Number of commits fetched from the remote (shallow fetch).
+const CLONE_DEPTH: i32 = 10;
+
+/// Maximum number of times credentials are requested before giving up.
+const MAX_AUTH_ATTEMPTS: usize = 3;
+
+/// Error that may occur running a git operation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The remote rejected the credentials provided (or required some).
+    Auth(String),
+    /// The repository or the reference requested could not be found.
+    NotFound(String),
+    /// The remote could not be reached or the transfer failed.
+    Network(String),
+    /// Any other error reported by libgit2 (i.e. a corrupted repository).
+    Git(git2::Error),
+    /// Error accessing the filesystem.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Auth(msg) => write!(f, "authentication failed: {msg}"),
+            Error::NotFound(msg) => write!(f, "not found: {msg}"),
+            Error::Network(msg) => write!(f, "network error: {msg}"),
+            Error::Git(err) => write!(f, "git error: {}", err.message()),
+            Error::Io(err) => write!(f, "io error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Git(err) => Some(err),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<git2::Error> for Error {
+    fn from(err: git2::Error) -> Self {
+        let msg = err.message().to_string();
+        match (err.code(), err.class()) {
+            (ErrorCode::Auth, _) => Error::Auth(msg),
+            (ErrorCode::NotFound, _) => Error::NotFound(msg),
+            (_, ErrorClass::Http) if msg.contains("401") || msg.contains("403") => Error::Auth(msg),
+            (_, ErrorClass::Http) if msg.contains("404") => Error::NotFound(msg),
+            (_, ErrorClass::Net | ErrorClass::Http | ErrorClass::Ssl | ErrorClass::Ssh) => {
+                Error::Network(msg)
+            }
+            _ => Error::Git(err),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<task::JoinError> for Error {
+    fn from(err: task::JoinError) -> Self {
+        Error::Git(git2::Error::from_str(&err.to_string()))
+    }
+}
+
+/// Credentials used to authenticate with the remote when it requires it.
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl Credentials {
+    /// Build the credentials used to access the repository at the url
+    /// provided from the token of its git hosting provider, when available.
+    pub fn from_token(
+        url: &str,
+        github_token: Option<&str>,
+        gitlab_token: Option<&str>,
+    ) -> Option<Self> {
+        let url = Url::parse(url).ok()?;
+        if url.scheme() != "https" {
+            return None;
+        }
+        let host = url.host_str()?.to_lowercase();
+        let (username, token) = if host == "github.com" {
+            ("x-access-token", github_token)
+        } else if host == "gitlab.com" || host.starts_with("gitlab.") {
+            ("oauth2", gitlab_token)
+        } else {
+            return None;
+        };
+        token.map(|token| Credentials {
+            username: username.to_string(),
+            password: token.to_string(),
+        })
+    }
+}
+
+/// Transfer progress of an operation on a remote repository.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub received_objects: usize,
+    pub total_objects: usize,
+    pub received_bytes: usize,
+}
+
+/// Callback used to report the transfer progress.
+pub type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// Options used when running operations on remote repositories.
+#[derive(Clone, Default)]
+pub struct RemoteOptions {
+    /// Credentials used when the remote requires authentication. When not
+    /// provided, the ssh agent and the git credential helpers configured are
+    /// used.
+    pub credentials: Option<Credentials>,
+
+    /// Callback called as objects are received from the remote.
+    pub progress: Option<ProgressCallback>,
+}
+
+impl fmt::Debug for RemoteOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteOptions")
+            .field("credentials", &self.credentials.as_ref().map(|_| "***"))
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Clone the git repository at the url provided in the destination path.
+/// Only the default branch is fetched unless a reference (branch or tag) is
+/// provided, in which case it will be checked out instead.
+pub async fn clone(
+    url: &str,
+    dst: &Path,
+    reference: Option<&str>,
+    opts: &RemoteOptions,
+) -> Result<(), Error> {
+    let (url, dst, reference, opts) = (
+        url.to_string(),
+        dst.to_path_buf(),
+        reference.map(ToString::to_string),
+        opts.clone(),
+    );
+    task::spawn_blocking(move || clone_blocking(&url, &dst, reference.as_deref(), &opts)).await?
+}
+
+/// Blocking version of clone.
+fn clone_blocking(
+    url: &str,
+    dst: &Path,
+    reference: Option<&str>,
+    opts: &RemoteOptions,
+) -> Result<(), Error> {
+    let repo = Repository::init(dst)?;
+    let mut remote = repo.remote("origin", url)?;
+    let branch = match reference {
+        Some(reference) => reference.to_string(),
+        None => default_branch(&mut remote, opts)?,
+    };
+    let mut refspecs = vec![format!("+refs/heads/{branch}:refs/remotes/origin/{branch}")];
+    if reference.is_some() {
+        refspecs.push(format!("+refs/tags/{branch}:refs/tags/{branch}"));
+    }
+    fetch(&mut remote, &refspecs, opts)?;
+
+    // Check out the branch (or the tag, detached) requested
+    if let Ok(remote_branch) = repo.find_reference(&format!("refs/remotes/origin/{branch}")) {
+        let commit = remote_branch.peel_to_commit()?;
+        repo.branch(&branch, &commit, true)?;
+        repo.set_head(&format!("refs/heads/{branch}"))?;
+    } else if let Ok(tag) = repo.find_reference(&format!("refs/tags/{branch}")) {
+        repo.set_head_detached(tag.peel_to_commit()?.id())?;
+    } else {
+        return Err(Error::NotFound(format!("reference {branch}")));
+    }
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    Ok(())
+}
+
+/// Get the digest of the remote HEAD of the git repository at the url
+/// provided.
+pub async fn remote_digest(url: &str, opts: &RemoteOptions) -> Result<String, Error> {
+    let (url, opts) = (url.to_string(), opts.clone());
+    task::spawn_blocking(move || {
+        let mut remote = Remote::create_detached(url.as_str())?;
+        let conn = remote.connect_auth(Direction::Fetch, Some(callbacks(&opts)), None)?;
+        let head = conn
+            .list()?
+            .iter()
+            .find(|head| head.name() == "HEAD")
+            .map(|head| head.oid().to_string());
+        head.ok_or_else(|| Error::NotFound(format!("remote HEAD of {url}")))
+    })
+    .await?
+}
+
+/// Fetch the remote HEAD of the git repository at the url provided into the
+/// bare mirror located at the path given, creating it if needed. Only the
+/// objects missing in the mirror are downloaded. The transfer stats of the
+/// fetch are returned. This function blocks, so that callers can run it in
+/// a blocking task along with the operations that must be coordinated with
+/// it (i.e. locking the mirror).
+pub fn fetch_head(url: &str, mirror: &Path, opts: &RemoteOptions) -> Result<Progress, Error> {
+    let repo = match mirror.exists() {
+        true => Repository::open_bare(mirror)?,
+        false => Repository::init_bare(mirror)?,
+    };
+    let mut remote = repo.remote_anonymous(url)?;
+    let branch = default_branch(&mut remote, opts)?;
+    fetch(
+        &mut remote,
+        &[format!("+refs/heads/{branch}:{MIRROR_HEAD_REF}")],
+        opts,
+    )
+}
+
+/// Check out the last remote HEAD fetched into the mirror located at the
+/// path provided in a new repository created in the destination given. The
+/// new repository uses the mirror's objects (like `git clone --shared`), so
+/// they are not copied, and it can be removed at any time. When the mirror is
+/// shallow, the new repository is shallow as well. Like fetch_head, this
+/// function blocks.
+pub fn checkout_shared(mirror: &Path, dst: &Path) -> Result<(), Error> {
+    let commit_id = Repository::open_bare(mirror)?
+        .find_reference(MIRROR_HEAD_REF)?
+        .peel_to_commit()?
+        .id();
+    let repo = Repository::init(dst)?;
+    let objects = fs::canonicalize(mirror.join("objects"))?;
+    fs::write(
+        repo.path().join("objects").join("info").join("alternates"),
+        format!("{}\n", objects.display()),
+    )?;
+    let shallow = mirror.join("shallow");
+    if shallow.exists() {
+        fs::copy(shallow, repo.path().join("shallow"))?;
+    }
+    checkout_detached(&Repository::open(dst)?, commit_id)
+}
+
+/// Get the id of the commit the HEAD of the git repository located at the
+/// path provided points to.
+pub fn head_commit(path: &Path) -> Result<String, Error> {
+    let repo = Repository::open(path)?;
+    let commit = repo.head()?.peel_to_commit()?;
+    Ok(commit.id().to_string())
+}
+
+/// Return the name of the default branch of the remote provided.
+fn default_branch(remote: &mut Remote, opts: &RemoteOptions) -> Result<String, Error> {
+    let conn = remote.connect_auth(Direction::Fetch, Some(callbacks(opts)), None)?;
+    let default_branch = conn.default_branch()?;
+    let default_branch = default_branch
+        .as_str()
+        .and_then(|name| name.strip_prefix("refs/heads/"))
+        .ok_or_else(|| Error::NotFound("remote default branch".to_string()))?;
+    Ok(default_branch.to_string())
+}
+
+/// Fetch the refspecs provided from the remote given (without tags),
+/// returning the transfer stats. Only the last commits are fetched, unless the
+/// remote is a local repository (libgit2 doesn't support shallow fetches from
+/// local repositories).
+fn fetch(
+    remote: &mut Remote,
+    refspecs: &[String],
+    opts: &RemoteOptions,
+) -> Result<Progress, Error> {
+    let mut fetch_opts = FetchOptions::new();
+    fetch_opts
+        .remote_callbacks(callbacks(opts))
+        .download_tags(AutotagOption::None)
+        .update_fetchhead(false);
+    if !matches!(remote.url(), Some(url) if is_local(url)) {
+        fetch_opts.depth(CLONE_DEPTH);
+    }
+    remote.fetch(refspecs, Some(&mut fetch_opts), None)?;
+    Ok(progress(&remote.stats()))
+}
+
+/// Check if the url provided points to a local repository.
+fn is_local(url: &str) -> bool {
+    url.starts_with("file://") || Path::new(url).exists()
+}
+
+/// Point the HEAD of the repository provided to the commit given (detached)
+/// and check it out.
+fn checkout_detached(repo: &Repository, commit_id: Oid) -> Result<(), Error> {
+    repo.set_head_detached(commit_id)?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    Ok(())
+}
+
+/// Prepare the callbacks used to authenticate with the remote and report the
+/// transfer progress.
+fn callbacks(opts: &RemoteOptions) -> RemoteCallbacks<'_> {
+    let mut callbacks = RemoteCallbacks::new();
+    let mut attempts = 0;
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        attempts += 1;
+        if attempts > MAX_AUTH_ATTEMPTS {
+            return Err(git2::Error::new(
+                ErrorCode::Auth,
+                ErrorClass::Net,
+                "credentials rejected by the remote",
+            ));
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if let Some(creds) = &opts.credentials {
+                return Cred::userpass_plaintext(&creds.username, &creds.password);
+            }
+            let config = git2::Config::open_default()?;
+            return Cred::credential_helper(&config, url, username_from_url);
+        }
+        if allowed.contains(CredentialType::SSH_KEY) {
+            return Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"));
+        }
+        Cred::default()
+    });
+    if let Some(report) = &opts.progress {
+        callbacks.transfer_progress(move |stats| {
+            report(&progress(&stats));
+            true
+        });
+    }
+    callbacks
+}
+
+/// Convert the libgit2 transfer progress provided.
+fn progress(stats: &git2::Progress) -> Progress {
+    Progress {
+        received_objects: stats.received_objects(),
+        total_objects: stats.total_objects(),
+        received_bytes: stats.received_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        env,
+        net::{TcpListener, TcpStream},
+        process,
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+        time::Duration,
+    };
+
+    /// Run the git command provided in the path given.
+    fn git(path: &Path, args: &[&str]) {
+        let status = process::Command::new("git")
+            .current_dir(path)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Create a git repository in the path provided with a couple of commits
+    /// and a tag, returning its url.
+    fn setup_repository(path: &Path) -> String {
+        fs::create_dir_all(path).unwrap();
+        git(path, &["init", "-q", "-b", "main"]);
+        git(path, &["config", "user.email", "test@example.com"]);
+        git(path, &["config", "user.name", "test"]);
+        fs::write(path.join("README.md"), "main").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-q", "-m", "initial"]);
+        git(path, &["tag", "v1"]);
+        fs::write(path.join("README.md"), "updated").unwrap();
+        git(path, &["commit", "-q", "-am", "update"]);
+        format!("file://{}", path.display())
+    }
+
+    #[test]
+    fn credentials_from_token() {
+        let creds =
+            Credentials::from_token("https://github.com/org/repo", Some("gh"), Some("gl")).unwrap();
+        assert_eq!(
+            (creds.username.as_str(), creds.password.as_str()),
+            ("x-access-token", "gh")
+        );
+        let creds =
+            Credentials::from_token("https://gitlab.com/group/repo", Some("gh"), Some("gl"))
+                .unwrap();
+        assert_eq!(
+            (creds.username.as_str(), creds.password.as_str()),
+            ("oauth2", "gl")
+        );
+        assert!(Credentials::from_token("https://github.com/org/repo", None, Some("gl")).is_none());
+        assert!(
+            Credentials::from_token("https://example.com/org/repo", Some("gh"), None).is_none()
+        );
+        assert!(Credentials::from_token("file:///tmp/repo", Some("gh"), None).is_none());
+    }
+
+    #[tokio::test]
+    async fn clone_reference_and_get_remote_digest() {
+        let base = env::temp_dir().join(format!("clomonitor-git-{}", process::id()));
+        let url = setup_repository(&base.join("src"));
+        let opts = RemoteOptions::default();
+
+        let dst = base.join("head");
+        clone(&url, &dst, None, &opts).await.unwrap();
+        assert_eq!(
+            fs::read_to_string(dst.join("README.md")).unwrap(),
+            "updated"
+        );
+        let repo = Repository::open(&dst).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+
+        let dst = base.join("tag");
+        clone(&url, &dst, Some("v1"), &opts).await.unwrap();
+        assert_eq!(fs::read_to_string(dst.join("README.md")).unwrap(), "main");
+
+        assert!(matches!(
+            clone(&url, &base.join("unknown"), Some("unknown"), &opts).await,
+            Err(Error::NotFound(_))
+        ));
+        assert_eq!(
+            remote_digest(&url, &opts).await.unwrap(),
+            head_commit(&base.join("head")).unwrap()
+        );
+        assert!(remote_digest("file:///nonexistent", &opts).await.is_err());
+
+        fs::remove_dir_all(base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_head_into_mirror_and_checkout_shared() {
+        let base = env::temp_dir().join(format!("clomonitor-git-mirror-{}", process::id()));
+        let src = base.join("src");
+        let url = setup_repository(&src);
+        let mirror = base.join("mirror.git");
+        let received = Arc::new(AtomicUsize::new(0));
+        let opts = RemoteOptions {
+            credentials: None,
+            progress: Some({
+                let received = received.clone();
+                Arc::new(move |p: &Progress| received.store(p.received_objects, Ordering::SeqCst))
+            }),
+        };
+
+        let stats = fetch_head(&url, &mirror, &opts).unwrap();
+        assert!(stats.received_objects > 0);
+        assert_eq!(received.load(Ordering::SeqCst), stats.received_objects);
+        checkout_shared(&mirror, &base.join("wt1")).unwrap();
+        assert_eq!(
+            fs::read_to_string(base.join("wt1").join("README.md")).unwrap(),
+            "updated"
+        );
+
+        fs::write(src.join("README.md"), "new").unwrap();
+        git(&src, &["commit", "-q", "-am", "new"]);
+        let stats = fetch_head(&url, &mirror, &opts).unwrap();
+        assert_eq!(stats.received_objects, 3);
+        checkout_shared(&mirror, &base.join("wt2")).unwrap();
+        assert_eq!(
+            fs::read_to_string(base.join("wt2").join("README.md")).unwrap(),
+            "new"
+        );
+        assert_eq!(
+            head_commit(&base.join("wt2")).unwrap(),
+            remote_digest(&url, &opts).await.unwrap()
+        );
+
+        fs::remove_dir_all(base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn clone_and_fetch_head_are_shallow() {
+        let base = env::temp_dir().join(format!("clomonitor-git-shallow-{}", process::id()));
+        let src = base.join("src");
+        setup_repository(&src);
+        for i in 0..CLONE_DEPTH {
+            fs::write(src.join("README.md"), i.to_string()).unwrap();
+            git(&src, &["commit", "-q", "-am", "more"]);
+        }
+        let opts = RemoteOptions::default();
+
+        // Serve the repository using the git protocol (depth is ignored when
+        // fetching from local paths)
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let exec_path = process::Command::new("git")
+            .arg("--exec-path")
+            .output()
+            .unwrap()
+            .stdout;
+        let exec_path = String::from_utf8(exec_path).unwrap();
+        let mut daemon = process::Command::new(Path::new(exec_path.trim()).join("git-daemon"))
+            .arg("--export-all")
+            .arg("--listen=127.0.0.1")
+            .arg(format!("--port={port}"))
+            .arg(format!("--base-path={}", base.display()))
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .spawn()
+            .unwrap();
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            thread::sleep(Duration::from_millis(50));
+        }
+        let url = format!("git://127.0.0.1:{port}/src");
+
+        // Count the commits available from the HEAD of the repository
+        let commits = |repo: &Repository| {
+            let mut revwalk = repo.revwalk().unwrap();
+            revwalk.push_head().unwrap();
+            revwalk.count() as i32
+        };
+
+        let dst = base.join("clone");
+        clone(&url, &dst, None, &opts).await.unwrap();
+        let repo = Repository::open(&dst).unwrap();
+        assert!(repo.is_shallow());
+        assert_eq!(commits(&repo), CLONE_DEPTH);
+
+        let mirror = base.join("mirror.git");
+        fetch_head(&url, &mirror, &opts).unwrap();
+        assert!(Repository::open_bare(&mirror).unwrap().is_shallow());
+        checkout_shared(&mirror, &base.join("wt")).unwrap();
+        let repo = Repository::open(base.join("wt")).unwrap();
+        assert!(repo.is_shallow());
+        assert_eq!(commits(&repo), CLONE_DEPTH);
+
+        // Shallow mirrors can be updated incrementally
+        fs::write(src.join("README.md"), "new").unwrap();
+        git(&src, &["commit", "-q", "-am", "new"]);
+        fetch_head(&url, &mirror, &opts).unwrap();
+        checkout_shared(&mirror, &base.join("wt2")).unwrap();
+        assert_eq!(
+            fs::read_to_string(base.join("wt2").join("README.md")).unwrap(),
+            "new"
+        );
+
+        daemon.kill().unwrap();
+        daemon.wait().unwrap();
+        fs::remove_dir_all(base).unwrap();
+    }
+}
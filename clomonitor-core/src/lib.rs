@@ -1,3 +1,4 @@
 mod config;
+pub mod git;
 pub mod linter;
 pub mod score;
@@ -0,0 +1,467 @@
+use super::{builtin_checks, license::APPROVED_LICENSES, patterns::*, Check};
+use crate::{
+    config::*,
+    linter::{CheckSet, Section},
+};
+use clap::ArgEnum;
+use regex::{Regex, RegexSet};
+use serde::Serialize;
+
+/// Information about a built-in check, including what it looks for.
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct CheckInfo {
+    pub id: String,
+    pub name: String,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub section: Section,
+    pub weight: usize,
+    pub check_sets: Vec<CheckSet>,
+    pub remote_only: bool,
+    pub criteria: Vec<Criterion>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+}
+
+/// Condition that makes a check pass. A check passes when any of its criteria
+/// is met (they are tried in order).
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Criterion {
+    pub source: Source,
+    pub description: &'static str,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub case_sensitive: Option<bool>,
+}
+
+/// Source of the information a criterion relies on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// Files in the repository (patterns are globs).
+    File,
+    /// Content of the repository's README file (patterns are regexps).
+    Readme,
+    /// Other information available in the repository (i.e. commits).
+    Repository,
+    /// Information provided by remote services (i.e. the git hosting
+    /// provider or the project's website).
+    Remote,
+}
+
+impl CheckInfo {
+    /// Return the file globs used by the check.
+    pub fn file_globs(&self) -> Vec<&str> {
+        self.patterns(Source::File)
+    }
+
+    /// Return the regular expressions used on the README file by the check.
+    pub fn readme_patterns(&self) -> Vec<&str> {
+        self.patterns(Source::Readme)
+    }
+
+    /// Return the criteria that rely on remote services.
+    pub fn remote_sources(&self) -> Vec<&Criterion> {
+        self.criteria
+            .iter()
+            .filter(|c| c.source == Source::Remote)
+            .collect()
+    }
+
+    /// Return the patterns of all the criteria using the source provided.
+    fn patterns(&self, source: Source) -> Vec<&str> {
+        self.criteria
+            .iter()
+            .filter(|c| c.source == source)
+            .flat_map(|c| c.patterns.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+/// Return the catalog of built-in checks, in the order they are run.
+pub fn catalog() -> Vec<CheckInfo> {
+    builtin_checks()
+        .iter()
+        .map(|check| check_info(check.as_ref()))
+        .collect()
+}
+
+/// Build the catalog entry of the built-in check provided.
+fn check_info(check: &dyn Check) -> CheckInfo {
+    let (title, description, criteria) = describe(check.id());
+    let check_sets = check.check_sets();
+    CheckInfo {
+        id: check.id().to_string(),
+        name: check.name().to_string(),
+        title,
+        description,
+        section: check.section(),
+        weight: check.weight(),
+        check_sets: CheckSet::value_variants()
+            .iter()
+            .filter(|check_set| check_sets.contains(check_set))
+            .cloned()
+            .collect(),
+        remote_only: check.remote_only(),
+        criteria,
+        remediation: check.remediation().map(ToString::to_string),
+        docs_url: check.docs_url(),
+    }
+}
+
+/// Description of the criterion that looks for a file in the repository.
+macro_rules! file_found {
+    ($what:literal) => {
+        concat!($what, " *file* is found in the repository.")
+    };
+}
+
+/// Description of the criterion that looks for a reference in the README file.
+macro_rules! reference_found {
+    ($what:literal) => {
+        concat!(
+            $what,
+            " *reference* is found in the repository's `README` file. ",
+            "This can be in the form of a **title header** or a link."
+        )
+    };
+}
+
+/// Description of the criterion that looks for a file in the `.github`
+/// default community health files repository.
+macro_rules! community_health_file_found {
+    ($what:literal) => {
+        concat!(
+            $what,
+            " file is found in the [`.github` default community health files repository]",
+            "(https://docs.github.com/en/communities/setting-up-your-project-for-healthy-contributions/creating-a-default-community-health-file)."
+        )
+    };
+}
+
+/// Return the title, description and criteria of the built-in check with the
+/// id provided.
+fn describe(check_id: &str) -> (&'static str, &'static str, Vec<Criterion>) {
+    match check_id {
+        ADOPTERS => (
+            "Adopters",
+            "List of organizations using this project in production or at stages of testing.",
+            vec![
+                file(file_found!("An adopters"), &ADOPTERS_FILE, false),
+                readme(reference_found!("An adopters"), regexps(&ADOPTERS_IN_README)),
+            ],
+        ),
+        CHANGELOG => (
+            "Changelog",
+            "A curated, chronologically ordered list of notable changes for each version.",
+            vec![
+                file(file_found!("A changelog"), &CHANGELOG_FILE, false),
+                readme(reference_found!("A changelog"), regexps(&CHANGELOG_IN_README)),
+                criterion(
+                    Source::Remote,
+                    "A changelog *reference* is found in the last Github release content body.",
+                    regexps(&CHANGELOG_IN_GH_RELEASE),
+                ),
+            ],
+        ),
+        CODE_OF_CONDUCT => (
+            "Code of conduct",
+            "Adopt a code of conduct to define community standards, signal a welcoming and inclusive project, and outline procedures for handling abuse.",
+            vec![
+                file(file_found!("A code of conduct"), &CODE_OF_CONDUCT_FILE, false),
+                readme(
+                    reference_found!("A code of conduct"),
+                    regexps(&CODE_OF_CONDUCT_IN_README),
+                ),
+                criterion(
+                    Source::Remote,
+                    community_health_file_found!("A code of conduct"),
+                    vec![],
+                ),
+            ],
+        ),
+        CONTRIBUTING => (
+            "Contributing",
+            "A contributing file in your repository provides potential project contributors with a short guide to how they can help with your project.",
+            vec![
+                file(file_found!("A contributing"), &CONTRIBUTING_FILE, false),
+                readme(
+                    reference_found!("A contributing"),
+                    regexps(&CONTRIBUTING_IN_README),
+                ),
+                criterion(
+                    Source::Remote,
+                    community_health_file_found!("A contributing"),
+                    vec![],
+                ),
+            ],
+        ),
+        GOVERNANCE => (
+            "Governance",
+            "Document that explains how the governance and committer process works in the repository.",
+            vec![
+                file(file_found!("A governance"), &GOVERNANCE_FILE, false),
+                readme(reference_found!("A governance"), regexps(&GOVERNANCE_IN_README)),
+            ],
+        ),
+        MAINTAINERS => (
+            "Maintainers",
+            "The maintainers file contains a list of the current maintainers of the repository.",
+            vec![
+                file(file_found!("A maintainers"), &MAINTAINERS_FILE, false),
+                readme(
+                    reference_found!("A maintainers"),
+                    regexps(&MAINTAINERS_IN_README),
+                ),
+            ],
+        ),
+        README => (
+            "Readme",
+            "The readme file introduces and explains a project. It contains information that is commonly required to understand what the project is about.",
+            vec![file(file_found!("A readme"), &README_FILE, true)],
+        ),
+        ROADMAP => (
+            "Roadmap",
+            "Defines a high-level overview of the project's goals and deliverables ideally presented on a timeline.",
+            vec![
+                file(file_found!("A roadmap"), &ROADMAP_FILE, false),
+                readme(reference_found!("A roadmap"), regexps(&ROADMAP_IN_README)),
+            ],
+        ),
+        WEBSITE => (
+            "Website",
+            "A url that users can visit to learn more about your project.",
+            vec![criterion(
+                Source::Remote,
+                "A website *url* is configured in the Github repository.",
+                vec![],
+            )],
+        ),
+        LICENSE_SPDX => (
+            "SPDX id",
+            "Identifier detected from the license file provided.",
+            vec![
+                file(
+                    "A license *file* is found in the repository and we can detect the license used.",
+                    &LICENSE_FILE,
+                    true,
+                ),
+                criterion(
+                    Source::Remote,
+                    "A license SPDX id can be obtained from Github.",
+                    vec![],
+                ),
+            ],
+        ),
+        LICENSE_APPROVED => (
+            "Approved license",
+            "Whether the repository uses an approved license or not.",
+            vec![criterion(
+                Source::Repository,
+                "The license identified matches any of the following:",
+                APPROVED_LICENSES.iter().map(ToString::to_string).collect(),
+            )],
+        ),
+        LICENSE_SCANNING => (
+            "License scanning",
+            "License scanning software scans and automatically identifies, manages and addresses open source licensing issues.",
+            vec![
+                readme(
+                    "A `FOSSA` or `Snyk` link is found in the repository's `README` file.",
+                    vec![regexp(&FOSSA_URL), regexp(&SNYK_URL)],
+                ),
+                criterion(
+                    Source::Repository,
+                    "A *link* pointing to the license scanning results is provided in the [.clomonitor.yml](https://github.com/cncf/clomonitor/blob/main/docs/metadata/.clomonitor.yml) metadata file.",
+                    vec![],
+                ),
+            ],
+        ),
+        ARTIFACTHUB_BADGE => (
+            "Artifact Hub badge",
+            "Projects can list their content on Artifact Hub to improve their discoverability.",
+            vec![readme(
+                "An `Artifact Hub` badge is found in the repository's `README` file.",
+                vec![regexp(&ARTIFACTHUB_URL)],
+            )],
+        ),
+        COMMUNITY_MEETING => (
+            "Community meeting",
+            "Community meetings are often held to engage community members, hear more voices and get more viewpoints.",
+            vec![readme(
+                "A *reference* to the community meeting is found in the repository's `README` file.",
+                regexps(&COMMUNITY_MEETING_TEXT),
+            )],
+        ),
+        DCO => (
+            "Developer Certificate of Origin",
+            "Mechanism for contributors to certify that they wrote or have the right to submit the code they are contributing.",
+            vec![
+                criterion(
+                    Source::Repository,
+                    "The last commits in the repository have the DCO signature (*Signed-off-by*). Merge pull request and merge branch commits are ignored for this check.",
+                    vec![],
+                ),
+                criterion(
+                    Source::Remote,
+                    "A *reference* to the DCO app is found in the last PR checks page on Github.",
+                    regexps(&DCO_IN_PR),
+                ),
+            ],
+        ),
+        OPENSSF_BADGE => (
+            "OpenSSF badge",
+            "The Open Source Security Foundation (OpenSSF) Best Practices badge is a way for Free/Libre and Open Source Software (FLOSS) projects to show that they follow best practices.",
+            vec![readme(
+                "An `OpenSSF` (CII) badge is found in the repository's `README` file.",
+                vec![regexp(&OPENSSF_URL)],
+            )],
+        ),
+        RECENT_RELEASE => (
+            "Recent release",
+            "The project should have released at least one version in the last year.",
+            vec![criterion(
+                Source::Remote,
+                "A release that is less than one year old is found on Github.",
+                vec![],
+            )],
+        ),
+        SLACK_PRESENCE => (
+            "Slack presence",
+            "Projects should have presence in the CNCF Slack or Kubernetes Slack.",
+            vec![readme(
+                "A *reference* to the CNCF Slack or Kubernetes Slack is found in the repository's `README` file.",
+                regexps(&SLACK_IN_README),
+            )],
+        ),
+        SBOM => (
+            "Software bill of materials (SBOM)",
+            "List of components in a piece of software, including licenses, versions, etc.",
+            vec![
+                criterion(
+                    Source::Remote,
+                    "The latest release on Github includes an asset which name contains *sbom*.",
+                    regexps(&SBOM_IN_GH_RELEASE),
+                ),
+                readme(
+                    "The repository's `README` file contains a *SBOM* section that explains where they are published to, format used, etc. This is detected by locating its **title header**.",
+                    regexps(&SBOM_IN_README),
+                ),
+            ],
+        ),
+        SECURITY_POLICY => (
+            "Security policy",
+            "Clearly documented security processes explaining how to report security issues to the project.",
+            vec![
+                file(
+                    file_found!("A security policy"),
+                    &SECURITY_POLICY_FILE,
+                    false,
+                ),
+                readme(
+                    reference_found!("A security policy"),
+                    regexps(&SECURITY_POLICY_IN_README),
+                ),
+                criterion(
+                    Source::Remote,
+                    community_health_file_found!("A security policy"),
+                    vec![],
+                ),
+            ],
+        ),
+        TRADEMARK_DISCLAIMER => (
+            "Trademark disclaimer",
+            "Projects sites should have the Linux Foundation trademark disclaimer.",
+            vec![criterion(
+                Source::Remote,
+                "The Linux Foundation trademark disclaimer is found in the content of the website configured in Github.",
+                regexps(&TRADEMARK_DISCLAIMER_IN_WEBSITE),
+            )],
+        ),
+        _ => unreachable!("built-in check {check_id} not described"),
+    }
+}
+
+/// Build a criterion with the information provided.
+fn criterion(source: Source, description: &'static str, patterns: Vec<String>) -> Criterion {
+    Criterion {
+        source,
+        description,
+        patterns,
+        case_sensitive: None,
+    }
+}
+
+/// Build a criterion that looks for files matching the globs provided.
+fn file(description: &'static str, globs: &[&str], case_sensitive: bool) -> Criterion {
+    Criterion {
+        case_sensitive: Some(case_sensitive),
+        ..criterion(
+            Source::File,
+            description,
+            globs.iter().map(ToString::to_string).collect(),
+        )
+    }
+}
+
+/// Build a criterion that looks for content in the README file matching the
+/// regular expressions provided.
+fn readme(description: &'static str, patterns: Vec<String>) -> Criterion {
+    criterion(Source::Readme, description, patterns)
+}
+
+/// Return the patterns of the regular expressions set provided.
+fn regexps(re: &RegexSet) -> Vec<String> {
+    re.patterns().to_vec()
+}
+
+/// Return the pattern of the regular expression provided.
+fn regexp(re: &Regex) -> String {
+    re.as_str().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_describes_all_builtin_checks() {
+        let catalog = catalog();
+        assert_eq!(catalog.len(), CHECK_WEIGHT.len());
+        for info in &catalog {
+            assert_eq!(info.weight, CHECK_WEIGHT[info.id.as_str()]);
+            assert!(!info.check_sets.is_empty());
+            assert!(!info.criteria.is_empty());
+
+            // Docs anchors must match the titles used in the docs
+            let anchor: String = info
+                .title
+                .to_lowercase()
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-')
+                .map(|c| if c == ' ' { '-' } else { c })
+                .collect();
+            assert_eq!(CHECK_DOCS_ANCHOR[info.id.as_str()], anchor);
+        }
+    }
+
+    #[test]
+    fn check_info_patterns() {
+        let catalog = catalog();
+        let info = catalog.iter().find(|info| info.id == CHANGELOG).unwrap();
+        assert_eq!(info.check_sets, vec![CheckSet::Code]);
+        assert_eq!(info.file_globs(), CHANGELOG_FILE.to_vec());
+        assert_eq!(info.readme_patterns(), CHANGELOG_IN_README.patterns());
+        assert_eq!(info.remote_sources().len(), 1);
+        assert!(!info.remote_only);
+    }
+}
@@ -2,19 +2,64 @@ use super::path::{self, Globs};
 use anyhow::Error;
 use lazy_static::lazy_static;
 use regex::{Regex, RegexSet};
-use std::fs;
+use std::{fs, path::PathBuf};
+
+/// Maximum length of the snippets included in the matches.
+const SNIPPET_MAX_LEN: usize = 200;
+
+/// Content match found in a file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Match {
+    /// Path of the file, relative to the globs root.
+    pub path: PathBuf,
+    /// Line number where the match starts (1-based).
+    pub line: usize,
+    /// Line where the match starts.
+    pub snippet: String,
+    /// Value captured by the first capture group, if any.
+    pub capture: Option<String>,
+}
+
+impl Match {
+    /// Create a new Match instance for the match found in the content
+    /// provided at the given offset.
+    fn new(path: PathBuf, content: &str, offset: usize, capture: Option<String>) -> Self {
+        let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = content[offset..]
+            .find('\n')
+            .map_or(content.len(), |i| offset + i);
+        Self {
+            path,
+            line: content[..offset].matches('\n').count() + 1,
+            snippet: content[line_start..line_end]
+                .trim()
+                .chars()
+                .take(SNIPPET_MAX_LEN)
+                .collect(),
+            capture,
+        }
+    }
+}
 
 /// Check if the content of any of the files that match the globs provided
-/// matches any of the regular expressions given, returning the captured value
-/// when there is a match. This function expects that the regular expressions
-/// provided contain one capture group.
-pub(crate) fn find(globs: Globs, regexps: Vec<&Regex>) -> Result<Option<String>, Error> {
+/// matches any of the regular expressions given, returning the match (with
+/// the captured value) when found. This function expects that the regular
+/// expressions provided contain one capture group.
+pub(crate) fn find(globs: Globs, regexps: Vec<&Regex>) -> Result<Option<Match>, Error> {
+    let root = globs.root.to_owned();
     for path in path::matches(globs)?.iter() {
         if let Ok(content) = fs::read_to_string(path) {
             for re in regexps.iter() {
                 if let Some(c) = re.captures(&content) {
                     if c.len() > 1 {
-                        return Ok(Some(c[1].to_string()));
+                        let offset = c.get(0).unwrap().start();
+                        let path = path::relative(&root, path)?;
+                        return Ok(Some(Match::new(
+                            path,
+                            &content,
+                            offset,
+                            Some(c[1].to_string()),
+                        )));
                     }
                 }
             }
@@ -24,14 +69,23 @@ pub(crate) fn find(globs: Globs, regexps: Vec<&Regex>) -> Result<Option<String>,
 }
 
 /// Check if the content of any of the files that match the globs provided
-/// matches any of the regular expressions given.
-pub(crate) fn matches(globs: Globs, re: &RegexSet) -> Result<bool, Error> {
-    Ok(path::matches(globs)?.iter().any(|path| {
+/// matches any of the regular expressions given, returning the first match.
+pub(crate) fn matches(globs: Globs, re: &RegexSet) -> Result<Option<Match>, Error> {
+    let root = globs.root.to_owned();
+    for path in path::matches(globs)?.iter() {
         if let Ok(content) = fs::read_to_string(path) {
-            return re.is_match(&content);
+            // The regex set tells us which expressions matched, but not where,
+            // so we need to locate the match using the individual expression
+            if let Some(i) = re.matches(&content).iter().next() {
+                let offset = Regex::new(&re.patterns()[i])?
+                    .find(&content)
+                    .map_or(0, |m| m.start());
+                let path = path::relative(&root, path)?;
+                return Ok(Some(Match::new(path, &content, offset, None)));
+            }
         }
-        false
-    }))
+    }
+    Ok(None)
 }
 
 /// Check if the content of the url provided matches any of the regular
@@ -65,7 +119,12 @@ mod tests {
             )
             .unwrap()
             .unwrap(),
-            "https://snyk.io/test/github/username/repo".to_string()
+            Match {
+                path: PathBuf::from("README.md"),
+                line: 3,
+                snippet: "[![Known Vulnerabilities](https://snyk.io/test/github/username/repo/badge.svg)](https://snyk.io/test/github/username/repo)".to_string(),
+                capture: Some("https://snyk.io/test/github/username/repo".to_string()),
+            }
         );
     }
 
@@ -103,35 +162,42 @@ mod tests {
 
     #[test]
     fn find_invalid_glob_pattern() {
-        assert!(matches!(
-            find(
-                Globs {
-                    root: Path::new(TESTDATA_PATH),
-                    patterns: &["invalid***"],
-                    case_sensitive: true,
-                },
-                vec![&Regex::new("pattern").unwrap()]
-            ),
-            Err(_)
-        ));
-    }
-
-    #[test]
-    fn matches_match() {
-        assert!(matches(
+        assert!(find(
             Globs {
                 root: Path::new(TESTDATA_PATH),
-                patterns: &README_FILE,
+                patterns: &["invalid***"],
                 case_sensitive: true,
             },
-            &*ADOPTERS_IN_README
+            vec![&Regex::new("pattern").unwrap()]
         )
-        .unwrap());
+        .is_err());
+    }
+
+    #[test]
+    fn matches_match() {
+        assert_eq!(
+            matches(
+                Globs {
+                    root: Path::new(TESTDATA_PATH),
+                    patterns: &README_FILE,
+                    case_sensitive: true,
+                },
+                &ADOPTERS_IN_README
+            )
+            .unwrap()
+            .unwrap(),
+            Match {
+                path: PathBuf::from("README.md"),
+                line: 7,
+                snippet: "## Adopters".to_string(),
+                capture: None,
+            }
+        );
     }
 
     #[test]
     fn matches_no_match() {
-        assert!(!matches(
+        assert!(matches(
             Globs {
                 root: Path::new(TESTDATA_PATH),
                 patterns: &README_FILE,
@@ -139,12 +205,13 @@ mod tests {
             },
             &RegexSet::new(["non-existing pattern"]).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_none());
     }
 
     #[test]
     fn matches_file_not_found() {
-        assert!(!matches(
+        assert!(matches(
             Globs {
                 root: Path::new(TESTDATA_PATH),
                 patterns: &["nonexisting"],
@@ -152,21 +219,20 @@ mod tests {
             },
             &RegexSet::new(["pattern"]).unwrap(),
         )
-        .unwrap());
+        .unwrap()
+        .is_none());
     }
 
     #[test]
     fn matches_invalid_glob_pattern() {
-        assert!(matches!(
-            matches(
-                Globs {
-                    root: Path::new(TESTDATA_PATH),
-                    patterns: &["invalid***"],
-                    case_sensitive: true,
-                },
-                &RegexSet::new(["pattern"]).unwrap(),
-            ),
-            Err(_)
-        ));
+        assert!(matches(
+            Globs {
+                root: Path::new(TESTDATA_PATH),
+                patterns: &["invalid***"],
+                case_sensitive: true,
+            },
+            &RegexSet::new(["pattern"]).unwrap(),
+        )
+        .is_err());
     }
 }
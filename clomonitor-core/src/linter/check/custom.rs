@@ -0,0 +1,207 @@
+use super::{
+    find_file_or_reference,
+    path::{self, Globs},
+    readme_reference, Check, CheckDetails, CheckOptions, CheckOutput, CheckResult,
+};
+use crate::linter::{CheckSet, Section};
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::ArgEnum;
+use regex::RegexSet;
+use serde::{Deserialize, Deserializer};
+
+/// Custom check declared in a metadata file.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomCheck {
+    pub id: String,
+    pub name: Option<String>,
+    pub section: Section,
+    pub weight: usize,
+    pub failure_message: String,
+    pub remediation: Option<String>,
+    pub docs_url: Option<String>,
+    pub check_sets: Option<Vec<CheckSet>>,
+
+    #[serde(flatten)]
+    pub rule: CustomRule,
+}
+
+/// Rule used by a custom check to decide if it passes or not.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "rule", rename_all = "camelCase")]
+pub enum CustomRule {
+    /// A file matching any of the globs provided must exist in the repository.
+    File { patterns: Vec<String> },
+
+    /// The README file must match any of the regular expressions provided.
+    Readme {
+        #[serde(deserialize_with = "deserialize_regex_set")]
+        regexps: RegexSet,
+    },
+
+    /// A file matching any of the globs provided must exist in the repository
+    /// or the README file must match any of the regular expressions provided.
+    FileOrReference {
+        patterns: Vec<String>,
+        #[serde(deserialize_with = "deserialize_regex_set")]
+        regexps: RegexSet,
+    },
+}
+
+impl PartialEq for CustomRule {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::File { patterns: p1 }, Self::File { patterns: p2 }) => p1 == p2,
+            (Self::Readme { regexps: r1 }, Self::Readme { regexps: r2 }) => {
+                r1.patterns() == r2.patterns()
+            }
+            (
+                Self::FileOrReference {
+                    patterns: p1,
+                    regexps: r1,
+                },
+                Self::FileOrReference {
+                    patterns: p2,
+                    regexps: r2,
+                },
+            ) => p1 == p2 && r1.patterns() == r2.patterns(),
+            _ => false,
+        }
+    }
+}
+
+#[async_trait]
+impl Check for CustomCheck {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.id)
+    }
+
+    fn section(&self) -> Section {
+        self.section
+    }
+
+    fn weight(&self) -> usize {
+        self.weight
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        self.remediation.as_deref()
+    }
+
+    fn docs_url(&self) -> Option<String> {
+        self.docs_url.clone()
+    }
+
+    fn check_sets(&self) -> Vec<CheckSet> {
+        match &self.check_sets {
+            Some(check_sets) => check_sets.clone(),
+            None => CheckSet::value_variants().to_vec(),
+        }
+    }
+
+    async fn run(&self, opts: &CheckOptions) -> Result<CheckOutput, Error> {
+        let mut r = match &self.rule {
+            CustomRule::File { patterns } => {
+                let patterns = as_str_slice(patterns);
+                match path::find(Globs {
+                    root: &opts.root,
+                    patterns: &patterns,
+                    case_sensitive: false,
+                })? {
+                    Some(path) => CheckResult::from_path(Some(path), opts),
+                    None => CheckResult::failed(CheckDetails::from_globs(&patterns)),
+                }
+            }
+            CustomRule::Readme { regexps } => readme_reference(opts, regexps)?,
+            CustomRule::FileOrReference { patterns, regexps } => {
+                find_file_or_reference(opts, &as_str_slice(patterns), regexps)?
+            }
+        };
+        if !r.passed {
+            r.failure_message = Some(self.failure_message.clone());
+        }
+        Ok(r.into())
+    }
+}
+
+/// Helper that returns the strings provided as a vector of string slices.
+fn as_str_slice(v: &[String]) -> Vec<&str> {
+    v.iter().map(String::as_str).collect()
+}
+
+/// Deserialize a list of regular expressions into a RegexSet.
+fn deserialize_regex_set<'de, D>(deserializer: D) -> Result<RegexSet, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let exprs = Vec::<String>::deserialize(deserializer)?;
+    RegexSet::new(exprs).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_file_or_reference_rule() {
+        let check: CustomCheck = serde_yaml::from_str(
+            r#"
+            id: release_process
+            section: best_practices
+            weight: 3
+            failureMessage: release process not documented
+            checkSets: [code]
+            rule: fileOrReference
+            patterns: ["RELEASE*"]
+            regexps: ["(?im)^#+.*release process.*$"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(check.name(), "release_process");
+        assert_eq!(check.section(), Section::BestPractices);
+        assert_eq!(check.check_sets(), vec![CheckSet::Code]);
+        assert_eq!(
+            check.rule,
+            CustomRule::FileOrReference {
+                patterns: vec!["RELEASE*".to_string()],
+                regexps: RegexSet::new(["(?im)^#+.*release process.*$"]).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_rule_with_invalid_regexp_fails() {
+        assert!(serde_yaml::from_str::<CustomCheck>(
+            r#"
+            id: invalid
+            section: documentation
+            weight: 1
+            failureMessage: invalid
+            rule: readme
+            regexps: ["(unclosed"]
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn check_sets_default_to_all() {
+        let check: CustomCheck = serde_yaml::from_str(
+            r#"
+            id: adopters_file
+            section: documentation
+            weight: 1
+            failureMessage: adopters file not found
+            rule: file
+            patterns: ["ADOPTERS*"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(check.check_sets(), CheckSet::value_variants().to_vec());
+    }
+}
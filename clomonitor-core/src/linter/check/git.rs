@@ -5,6 +5,17 @@ use std::path::Path;
 /// Maximum number of commits used to check if the repository requires DCO.
 pub const DCO_MAX_COMMITS: usize = 20;
 
+/// Return the name of the branch currently checked out in the git repository
+/// located in the path provided.
+pub(crate) fn current_branch(path: &Path) -> Option<String> {
+    let repo = git2::Repository::open(path).ok()?;
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(ToString::to_string)
+}
+
 /// Check if the last commits on the git repository located in the path
 /// provided have the DCO signature.
 pub(crate) fn commits_have_dco_signature(path: &Path) -> Result<bool, git2::Error> {
@@ -1,186 +1,356 @@
-use super::{content, patterns::*};
-use anyhow::{format_err, Error};
-use chrono::{Duration, Utc};
-use lazy_static::lazy_static;
-use octocrab::{
-    models::{repos::Release, Repository},
-    params::State,
+use super::{
+    content,
+    patterns::*,
+    provider::{Provider, Release, RepositoryMetadata, RepositoryUrl},
 };
-use regex::{Regex, RegexSet};
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+use serde::{de::DeserializeOwned, Deserialize};
 use std::path::Path;
 
-/// Build a url from the path and metadata provided.
-pub(crate) fn build_url(path: &Path, owner: &str, repo: &str, branch: &str) -> String {
-    format!(
-        "https://github.com/{}/{}/blob/{}/{}",
-        owner,
-        repo,
-        branch,
-        path.to_string_lossy(),
-    )
+/// GitHub API default url.
+const API_URL: &str = "https://api.github.com";
+
+/// GitHub raw content default url.
+const RAW_URL: &str = "https://raw.githubusercontent.com";
+
+/// GitHub git hosting provider.
+#[derive(Debug)]
+pub(crate) struct GitHub {
+    http_client: reqwest::Client,
+    api_url: String,
+    raw_url: String,
+    repo: RepositoryUrl,
 }
 
-/// Get repository's metadata from the Github API.
-pub(crate) async fn get_repo_metadata(repo_url: &str) -> Result<Repository, Error> {
-    let (owner, repo) = get_owner_and_repo(repo_url)?;
-    let github = octocrab::instance();
-    match github.repos(&owner, &repo).get().await {
-        Ok(repo) => Ok(repo),
-        Err(err) => Err(err.into()),
+impl GitHub {
+    /// Create a new GitHub provider instance for the repository url provided.
+    pub(crate) fn new(repo_url: &str, token: Option<String>) -> Result<Self, Error> {
+        Self::new_with_urls(repo_url, token, API_URL, RAW_URL)
     }
-}
 
-/// Check if the given default community health file is available in the
-/// .github repository, returning the url to the file when found.
-pub(crate) async fn has_community_health_file(
-    file: &str,
-    gh_md: &Repository,
-) -> Result<Option<String>, Error> {
-    // Setup HTTP client lazily
-    lazy_static! {
-        static ref HTTP_CLIENT: reqwest::Client = reqwest::Client::new();
-    }
-
-    // Get community health files repository metadata
-    let community_repo_url = format!(
-        "https://github.com/{}/.github",
-        gh_md.owner.as_ref().unwrap().login
-    );
-    let community_repo = match get_repo_metadata(&community_repo_url).await {
-        Ok(repo) => repo,
-        Err(_) => return Ok(None),
-    };
-
-    // Check if the file is in the repo
-    let file_raw_url = format!(
-        "https://raw.githubusercontent.com/{}/{}/{}/{}",
-        &community_repo.owner.as_ref().unwrap().login,
-        &community_repo.name,
-        community_repo.default_branch.as_ref().unwrap(),
-        file
-    );
-    match HTTP_CLIENT.head(file_raw_url).send().await?.status() {
-        http::StatusCode::OK => {
-            let url = build_url(
-                Path::new(file),
-                &community_repo.owner.as_ref().unwrap().login,
-                &community_repo.name,
-                community_repo.default_branch.as_ref().unwrap(),
+    /// Create a new GitHub provider instance for the repository url provided,
+    /// using the API and raw content urls given.
+    pub(crate) fn new_with_urls(
+        repo_url: &str,
+        token: Option<String>,
+        api_url: &str,
+        raw_url: &str,
+    ) -> Result<Self, Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github.v3+json"),
+        );
+        headers.insert(USER_AGENT, HeaderValue::from_static("clomonitor"));
+        if let Some(token) = token {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("token {token}"))?,
             );
-            Ok(Some(url))
         }
-        _ => Ok(None),
+        Ok(Self {
+            http_client: reqwest::Client::builder()
+                .default_headers(headers)
+                .build()?,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            raw_url: raw_url.trim_end_matches('/').to_string(),
+            repo: RepositoryUrl::parse(repo_url, false)?,
+        })
+    }
+
+    /// Send a GET request to the GitHub API endpoint provided and deserialize
+    /// the response.
+    async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, Error> {
+        Ok(self
+            .http_client
+            .get(format!("{}{}", self.api_url, endpoint))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    /// Get the metadata of the repository provided from the GitHub API.
+    async fn get_metadata(&self, owner: &str, repo: &str) -> Result<RepositoryMetadata, Error> {
+        let repo: GhRepository = self.get(&format!("/repos/{owner}/{repo}")).await?;
+        Ok(RepositoryMetadata {
+            owner: repo.owner.login,
+            name: repo.name,
+            default_branch: repo.default_branch,
+            homepage: repo.homepage.filter(|url| !url.is_empty()),
+            license_spdx_id: repo
+                .license
+                .and_then(|license| license.spdx_id)
+                .filter(|spdx_id| spdx_id != "NOASSERTION"),
+        })
     }
 }
 
-/// Check if the repository has released a new version in the last year.
-pub(crate) async fn has_recent_release(repo_url: &str) -> Result<Option<String>, Error> {
-    if let Some(last_release) = last_release(repo_url).await? {
-        if let Some(created_at) = last_release.created_at {
-            if created_at > Utc::now() - Duration::days(365) {
-                return Ok(Some(last_release.html_url.into()));
+#[async_trait]
+impl Provider for GitHub {
+    async fn get_repo_metadata(&self) -> Result<RepositoryMetadata, Error> {
+        self.get_metadata(&self.repo.owner, &self.repo.name).await
+    }
+
+    async fn last_release(&self) -> Result<Option<Release>, Error> {
+        let (owner, repo) = (&self.repo.owner, &self.repo.name);
+        let releases: Vec<GhRelease> = self
+            .get(&format!("/repos/{owner}/{repo}/releases?per_page=1"))
+            .await?;
+        Ok(releases.into_iter().next().map(|r| Release {
+            url: r.html_url,
+            created_at: r.created_at,
+            body: r.body,
+            assets: r.assets.into_iter().map(|asset| asset.name).collect(),
+        }))
+    }
+
+    async fn last_pr_has_dco_check(&self) -> Result<bool, Error> {
+        let (owner, repo) = (&self.repo.owner, &self.repo.name);
+        let prs: Vec<GhPullRequest> = self
+            .get(&format!(
+                "/repos/{owner}/{repo}/pulls?state=closed&per_page=1"
+            ))
+            .await?;
+        Ok(match prs.first() {
+            Some(pr) => {
+                let checks_url = format!(
+                    "{}/{}/{}/pull/{}/checks",
+                    self.repo.base_url, owner, repo, pr.number
+                );
+                content::remote_matches(&checks_url, &DCO_IN_PR).await?
             }
+            None => false,
+        })
+    }
+
+    async fn community_health_file(&self, file: &str) -> Result<Option<String>, Error> {
+        // Get community health files repository metadata
+        let community_repo = match self.get_metadata(&self.repo.owner, ".github").await {
+            Ok(repo) => repo,
+            Err(_) => return Ok(None),
+        };
+
+        // Check if the file is in the repo
+        let file_raw_url = format!(
+            "{}/{}/{}/{}/{}",
+            self.raw_url,
+            &community_repo.owner,
+            &community_repo.name,
+            &community_repo.default_branch,
+            file
+        );
+        match self.http_client.head(file_raw_url).send().await?.status() {
+            http::StatusCode::OK => Ok(Some(self.file_url(Path::new(file), &community_repo))),
+            _ => Ok(None),
         }
     }
-    Ok(None)
+
+    fn local_repo_metadata(&self, default_branch: &str) -> RepositoryMetadata {
+        self.repo.to_metadata(default_branch)
+    }
+
+    fn file_url(&self, path: &Path, md: &RepositoryMetadata) -> String {
+        build_url(
+            &self.repo.base_url,
+            path,
+            &md.owner,
+            &md.name,
+            &md.default_branch,
+        )
+    }
 }
 
-/// Check if the last PR in the repository has the DCO check.
-pub(crate) async fn last_pr_has_dco_check(repo_url: &str) -> Result<bool, Error> {
-    let (owner, repo) = get_owner_and_repo(repo_url)?;
-    let github = octocrab::instance();
-    let mut page = github
-        .pulls(&owner, &repo)
-        .list()
-        .state(State::Closed)
-        .per_page(1)
-        .send()
-        .await?;
-    Ok(match page.take_items().first() {
-        Some(pr) => {
-            let checks_url = format!(
-                "https://github.com/{}/{}/pull/{}/checks",
-                &owner, &repo, pr.number
-            );
-            content::remote_matches(&checks_url, &*DCO_IN_PR).await?
-        }
-        None => false,
-    })
+/// Build a url from the path and metadata provided.
+pub(crate) fn build_url(
+    base_url: &str,
+    path: &Path,
+    owner: &str,
+    repo: &str,
+    branch: &str,
+) -> String {
+    format!(
+        "{}/{}/{}/blob/{}/{}",
+        base_url,
+        owner,
+        repo,
+        branch,
+        path.to_string_lossy(),
+    )
 }
 
-/// Return the last release of the provided repository when available.
-pub(crate) async fn last_release(repo_url: &str) -> Result<Option<Release>, Error> {
-    let (owner, repo) = get_owner_and_repo(repo_url)?;
-    let github = octocrab::instance();
-    let mut page = github
-        .repos(&owner, &repo)
-        .releases()
-        .list()
-        .per_page(1)
-        .send()
-        .await?;
-    Ok(page.take_items().first().cloned())
+/// GitHub API repository.
+#[derive(Debug, Deserialize)]
+struct GhRepository {
+    name: String,
+    owner: GhUser,
+    default_branch: String,
+    homepage: Option<String>,
+    license: Option<GhLicense>,
 }
 
-/// Check if the last release body matches any of the regular expressions
-/// provided.
-pub(crate) async fn last_release_body_matches(
-    repo_url: &str,
-    re: &RegexSet,
-) -> Result<bool, Error> {
-    if let Some(last_release) = last_release(repo_url).await? {
-        if let Some(body) = last_release.body {
-            return Ok(re.is_match(&body));
-        }
-    }
-    Ok(false)
+/// GitHub API user.
+#[derive(Debug, Deserialize)]
+struct GhUser {
+    login: String,
 }
 
-/// Extract the owner and repository from the repository url provided.
-fn get_owner_and_repo(repo_url: &str) -> Result<(String, String), Error> {
-    lazy_static! {
-        static ref GITHUB_REPO_URL_RE: Regex =
-            Regex::new("^https://github.com/(?P<org>[^/]+)/(?P<repo>[^/]+)/?$").unwrap();
-    }
-    let c = GITHUB_REPO_URL_RE
-        .captures(repo_url)
-        .ok_or(format_err!("invalid repository url"))?;
-    Ok((c["org"].to_string(), c["repo"].to_string()))
+/// GitHub API license.
+#[derive(Debug, Deserialize)]
+struct GhLicense {
+    spdx_id: Option<String>,
+}
+
+/// GitHub API release.
+#[derive(Debug, Deserialize)]
+struct GhRelease {
+    html_url: String,
+    created_at: Option<DateTime<Utc>>,
+    body: Option<String>,
+    assets: Vec<GhAsset>,
+}
+
+/// GitHub API release asset.
+#[derive(Debug, Deserialize)]
+struct GhAsset {
+    name: String,
+}
+
+/// GitHub API pull request.
+#[derive(Debug, Deserialize)]
+struct GhPullRequest {
+    number: u64,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mockito::{mock, server_url};
+
+    fn provider() -> GitHub {
+        GitHub::new_with_urls(
+            &format!("{}/org/repo", server_url()),
+            Some("token".to_string()),
+            &format!("{}/api", server_url()),
+            &format!("{}/raw", server_url()),
+        )
+        .unwrap()
+    }
 
     #[test]
     fn build_url_works() {
         assert_eq!(
-            build_url(Path::new("path/test.md"), "owner", "repo", "main"),
+            build_url(
+                "https://github.com",
+                Path::new("path/test.md"),
+                "owner",
+                "repo",
+                "main"
+            ),
             "https://github.com/owner/repo/blob/main/path/test.md".to_string()
         );
     }
 
     #[test]
-    fn get_owner_and_repo_valid_url() {
+    fn new_invalid_url() {
+        assert!(GitHub::new("https://github.com/org", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn get_repo_metadata_works() {
+        let _m = mock("GET", "/api/repos/org/repo")
+            .match_header("authorization", "token token")
+            .with_body(
+                r#"{
+                    "name": "repo",
+                    "owner": {"login": "org"},
+                    "default_branch": "main",
+                    "homepage": "https://repo.io",
+                    "license": {"spdx_id": "NOASSERTION"}
+                }"#,
+            )
+            .create();
+
         assert_eq!(
-            get_owner_and_repo("https://github.com/org/repo").unwrap(),
-            ("org".to_string(), "repo".to_string())
+            provider().get_repo_metadata().await.unwrap(),
+            RepositoryMetadata {
+                owner: "org".to_string(),
+                name: "repo".to_string(),
+                default_branch: "main".to_string(),
+                homepage: Some("https://repo.io".to_string()),
+                license_spdx_id: None,
+            }
         );
     }
 
-    #[test]
-    fn get_owner_and_repo_valid_url_trailing_slash() {
+    #[tokio::test]
+    async fn last_release_works() {
+        let _m = mock("GET", "/api/repos/org/repo/releases?per_page=1")
+            .with_body(
+                r#"[{
+                    "html_url": "https://github.com/org/repo/releases/v1.0.0",
+                    "created_at": "2022-01-01T00:00:00Z",
+                    "body": "Changelog",
+                    "assets": [{"name": "sbom.spdx"}]
+                }]"#,
+            )
+            .create();
+
+        let release = provider().last_release().await.unwrap().unwrap();
+        assert_eq!(release.url, "https://github.com/org/repo/releases/v1.0.0");
+        assert_eq!(release.body, Some("Changelog".to_string()));
+        assert_eq!(release.assets, vec!["sbom.spdx".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn last_pr_has_dco_check_works() {
+        let _m1 = mock("GET", "/api/repos/org/repo/pulls?state=closed&per_page=1")
+            .with_body(r#"[{"number": 1}]"#)
+            .create();
+        let _m2 = mock("GET", "/org/repo/pull/1/checks")
+            .with_body(r#"<span>DCO</span>"#)
+            .create();
+
+        assert!(provider().last_pr_has_dco_check().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn community_health_file_found() {
+        let _m1 = mock("GET", "/api/repos/org/.github")
+            .with_body(
+                r#"{"name": ".github", "owner": {"login": "org"}, "default_branch": "main"}"#,
+            )
+            .create();
+        let _m2 = mock("HEAD", "/raw/org/.github/main/SECURITY.md").create();
+
         assert_eq!(
-            get_owner_and_repo("https://github.com/org/repo/").unwrap(),
-            ("org".to_string(), "repo".to_string())
+            provider()
+                .community_health_file("SECURITY.md")
+                .await
+                .unwrap(),
+            Some(format!(
+                "{}/org/.github/blob/main/SECURITY.md",
+                server_url()
+            ))
         );
     }
 
-    #[test]
-    fn get_owner_and_repo_invalid_url() {
-        assert!(matches!(
-            get_owner_and_repo("https://github.com/org"),
-            Err(_)
-        ));
+    #[tokio::test]
+    async fn community_health_file_repo_not_found() {
+        let _m = mock("GET", "/api/repos/org/.github")
+            .with_status(404)
+            .create();
+
+        assert_eq!(
+            provider()
+                .community_health_file("SECURITY.md")
+                .await
+                .unwrap(),
+            None
+        );
     }
 }
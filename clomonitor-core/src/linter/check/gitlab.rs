@@ -0,0 +1,326 @@
+use super::{
+    license,
+    provider::{Provider, Release, RepositoryMetadata, RepositoryUrl},
+};
+use anyhow::Error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::path::Path;
+
+/// GitLab git hosting provider.
+#[derive(Debug)]
+pub(crate) struct GitLab {
+    http_client: reqwest::Client,
+    api_url: String,
+    repo: RepositoryUrl,
+}
+
+impl GitLab {
+    /// Create a new GitLab provider instance for the repository url provided.
+    /// The API url is built from the repository url, so self-hosted instances
+    /// (available at `gitlab.*` hosts) are supported as well.
+    pub(crate) fn new(repo_url: &str, token: Option<String>) -> Result<Self, Error> {
+        let repo = RepositoryUrl::parse(repo_url, true)?;
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("clomonitor"));
+        if let Some(token) = token {
+            headers.insert("PRIVATE-TOKEN", HeaderValue::from_str(&token)?);
+        }
+        Ok(Self {
+            http_client: reqwest::Client::builder()
+                .default_headers(headers)
+                .build()?,
+            api_url: format!("{}/api/v4", repo.base_url),
+            repo,
+        })
+    }
+
+    /// Send a GET request to the project's GitLab API endpoint provided and
+    /// deserialize the response.
+    async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, Error> {
+        let project_id = format!("{}/{}", self.repo.owner, self.repo.name).replace('/', "%2F");
+        Ok(self
+            .http_client
+            .get(format!(
+                "{}/projects/{}{}",
+                self.api_url, project_id, endpoint
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+}
+
+#[async_trait]
+impl Provider for GitLab {
+    /// GitLab projects don't have a homepage, so the first link in the
+    /// project's description not pointing to the project itself is used.
+    async fn get_repo_metadata(&self) -> Result<RepositoryMetadata, Error> {
+        lazy_static! {
+            static ref LINK_RE: Regex = Regex::new(r#"https?://[^\s<>()\[\]"']+"#).unwrap();
+        }
+
+        let project: GlProject = self.get("?license=true").await?;
+        let homepage = project.description.as_deref().and_then(|description| {
+            LINK_RE
+                .find_iter(description)
+                .map(|m| m.as_str().trim_end_matches(&['.', ','][..]))
+                .find(|url| !url.starts_with(&project.web_url))
+                .map(ToString::to_string)
+        });
+        let license_spdx_id = project.license.and_then(|l| {
+            license::spdx_id(&l.key).or_else(|| l.nickname.as_deref().and_then(license::spdx_id))
+        });
+        Ok(RepositoryMetadata {
+            owner: project.namespace.full_path,
+            name: project.path,
+            default_branch: project.default_branch.unwrap_or_default(),
+            homepage,
+            license_spdx_id,
+        })
+    }
+
+    async fn last_release(&self) -> Result<Option<Release>, Error> {
+        let releases: Vec<GlRelease> = self.get("/releases?per_page=1").await?;
+        Ok(releases.into_iter().next().map(|r| Release {
+            url: r.links.self_url,
+            created_at: r.created_at,
+            body: r.description,
+            assets: r.assets.links.into_iter().map(|link| link.name).collect(),
+        }))
+    }
+
+    /// GitLab does not provide a DCO app, so the commits of the last merged
+    /// merge request are checked for the DCO signature instead.
+    async fn last_pr_has_dco_check(&self) -> Result<bool, Error> {
+        lazy_static! {
+            static ref DCO_SIGNATURE_RE: Regex = Regex::new(r"(?m)^Signed-off-by: ").unwrap();
+        }
+
+        let mrs: Vec<GlMergeRequest> = self.get("/merge_requests?state=merged&per_page=1").await?;
+        Ok(match mrs.first() {
+            Some(mr) => {
+                let commits: Vec<GlCommit> = self
+                    .get(&format!("/merge_requests/{}/commits", mr.iid))
+                    .await?;
+                !commits.is_empty()
+                    && commits
+                        .iter()
+                        .all(|commit| DCO_SIGNATURE_RE.is_match(&commit.message))
+            }
+            None => false,
+        })
+    }
+
+    /// GitLab does not support organization level community health files.
+    async fn community_health_file(&self, _file: &str) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    fn local_repo_metadata(&self, default_branch: &str) -> RepositoryMetadata {
+        self.repo.to_metadata(default_branch)
+    }
+
+    fn file_url(&self, path: &Path, md: &RepositoryMetadata) -> String {
+        format!(
+            "{}/{}/{}/-/blob/{}/{}",
+            self.repo.base_url,
+            md.owner,
+            md.name,
+            md.default_branch,
+            path.to_string_lossy(),
+        )
+    }
+}
+
+/// GitLab API project.
+#[derive(Debug, Deserialize)]
+struct GlProject {
+    path: String,
+    namespace: GlNamespace,
+    default_branch: Option<String>,
+    web_url: String,
+    description: Option<String>,
+    license: Option<GlLicense>,
+}
+
+/// GitLab API project license.
+#[derive(Debug, Deserialize)]
+struct GlLicense {
+    key: String,
+    nickname: Option<String>,
+}
+
+/// GitLab API namespace.
+#[derive(Debug, Deserialize)]
+struct GlNamespace {
+    full_path: String,
+}
+
+/// GitLab API release.
+#[derive(Debug, Deserialize)]
+struct GlRelease {
+    description: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    #[serde(rename = "_links")]
+    links: GlReleaseLinks,
+    assets: GlReleaseAssets,
+}
+
+/// GitLab API release links.
+#[derive(Debug, Deserialize)]
+struct GlReleaseLinks {
+    #[serde(rename = "self")]
+    self_url: String,
+}
+
+/// GitLab API release assets.
+#[derive(Debug, Deserialize)]
+struct GlReleaseAssets {
+    links: Vec<GlReleaseAssetLink>,
+}
+
+/// GitLab API release asset link.
+#[derive(Debug, Deserialize)]
+struct GlReleaseAssetLink {
+    name: String,
+}
+
+/// GitLab API merge request.
+#[derive(Debug, Deserialize)]
+struct GlMergeRequest {
+    iid: u64,
+}
+
+/// GitLab API commit.
+#[derive(Debug, Deserialize)]
+struct GlCommit {
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, server_url};
+
+    const PROJECT_PATH: &str = "/api/v4/projects/group%2Fsubgroup%2Frepo";
+
+    fn provider() -> GitLab {
+        GitLab::new(
+            &format!("{}/group/subgroup/repo", server_url()),
+            Some("token".to_string()),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_repo_metadata_works() {
+        let _m = mock("GET", format!("{PROJECT_PATH}?license=true").as_str())
+            .match_header("private-token", "token")
+            .with_body(
+                r#"{
+                    "path": "repo",
+                    "namespace": {"full_path": "group/subgroup"},
+                    "default_branch": "main",
+                    "web_url": "https://gitlab.com/group/subgroup/repo",
+                    "description": "See https://gitlab.com/group/subgroup/repo/-/wikis or https://repo.io.",
+                    "license": {"key": "apache-2.0", "nickname": null}
+                }"#,
+            )
+            .create();
+
+        assert_eq!(
+            provider().get_repo_metadata().await.unwrap(),
+            RepositoryMetadata {
+                owner: "group/subgroup".to_string(),
+                name: "repo".to_string(),
+                default_branch: "main".to_string(),
+                homepage: Some("https://repo.io".to_string()),
+                license_spdx_id: Some("Apache-2.0".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn get_repo_metadata_without_description_and_license() {
+        let _m = mock("GET", format!("{PROJECT_PATH}?license=true").as_str())
+            .with_body(
+                r#"{
+                    "path": "repo",
+                    "namespace": {"full_path": "group/subgroup"},
+                    "default_branch": "main",
+                    "web_url": "https://gitlab.com/group/subgroup/repo",
+                    "description": null,
+                    "license": null
+                }"#,
+            )
+            .create();
+
+        let md = provider().get_repo_metadata().await.unwrap();
+        assert_eq!(md.homepage, None);
+        assert_eq!(md.license_spdx_id, None);
+    }
+
+    #[tokio::test]
+    async fn last_release_works() {
+        let _m = mock(
+            "GET",
+            format!("{PROJECT_PATH}/releases?per_page=1").as_str(),
+        )
+        .with_body(
+            r#"[{
+                    "description": "Changelog",
+                    "created_at": "2022-01-01T00:00:00Z",
+                    "_links": {"self": "https://gitlab.com/group/subgroup/repo/-/releases/v1.0.0"},
+                    "assets": {"links": [{"name": "sbom.spdx.json"}]}
+                }]"#,
+        )
+        .create();
+
+        let release = provider().last_release().await.unwrap().unwrap();
+        assert_eq!(
+            release.url,
+            "https://gitlab.com/group/subgroup/repo/-/releases/v1.0.0"
+        );
+        assert_eq!(release.body, Some("Changelog".to_string()));
+        assert_eq!(release.assets, vec!["sbom.spdx.json".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn last_pr_has_dco_check_works() {
+        let _m1 = mock(
+            "GET",
+            format!("{PROJECT_PATH}/merge_requests?state=merged&per_page=1").as_str(),
+        )
+        .with_body(r#"[{"iid": 7}]"#)
+        .create();
+        let _m2 = mock(
+            "GET",
+            format!("{PROJECT_PATH}/merge_requests/7/commits").as_str(),
+        )
+        .with_body(r#"[{"message": "Fix\n\nSigned-off-by: Dev <dev@example.com>"}]"#)
+        .create();
+
+        assert!(provider().last_pr_has_dco_check().await.unwrap());
+    }
+
+    #[test]
+    fn file_url_works() {
+        let md = RepositoryMetadata {
+            owner: "group/subgroup".to_string(),
+            name: "repo".to_string(),
+            default_branch: "main".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            provider().file_url(Path::new("README.md"), &md),
+            format!("{}/group/subgroup/repo/-/blob/main/README.md", server_url())
+        );
+    }
+}
@@ -7,9 +7,13 @@ use std::fs;
 /// SPDX licenses data. Used to detect license used by repositories.
 const LICENSES_DATA: &[u8] = include_bytes!("data/licenses.bin.zstd");
 
+lazy_static! {
+    static ref LICENSES: Store = Store::from_cache(LICENSES_DATA).unwrap();
+}
+
 /// CNCF approved licenses.
 /// https://github.com/cncf/foundation/blob/master/allowed-third-party-license-policy.md
-static APPROVED_LICENSES: [&str; 11] = [
+pub(crate) static APPROVED_LICENSES: [&str; 11] = [
     "Apache-2.0",
     "BSD-2-Clause",
     "BSD-2-Clause-FreeBSD",
@@ -28,11 +32,17 @@ pub(crate) fn is_approved(spdx_id: &str) -> bool {
     APPROVED_LICENSES.contains(&spdx_id)
 }
 
+/// Return the SPDX id of the license identified by the key provided (case
+/// insensitive), if it is a known one.
+pub(crate) fn spdx_id(key: &str) -> Option<String> {
+    LICENSES
+        .licenses()
+        .find(|spdx_id| spdx_id.eq_ignore_ascii_case(key))
+        .cloned()
+}
+
 /// Detect repository's license and return its SPDX id if possible.
 pub(crate) fn detect(globs: Globs) -> Result<Option<String>, Error> {
-    lazy_static! {
-        static ref LICENSES: Store = Store::from_cache(LICENSES_DATA).unwrap();
-    }
     let mut spdx_id: Option<String> = None;
     matches(globs)?.iter().any(|path| {
         if let Ok(content) = fs::read_to_string(path) {
@@ -66,6 +76,13 @@ mod tests {
         assert!(!is_approved("AGPL-1.0-only"));
     }
 
+    #[test]
+    fn spdx_id_from_key() {
+        assert_eq!(spdx_id("apache-2.0"), Some("Apache-2.0".to_string()));
+        assert_eq!(spdx_id("MIT"), Some("MIT".to_string()));
+        assert_eq!(spdx_id("unknown"), None);
+    }
+
     #[test]
     fn detect_identified() {
         assert_eq!(
@@ -82,39 +99,33 @@ mod tests {
 
     #[test]
     fn detect_not_identified() {
-        assert!(matches!(
-            detect(Globs {
-                root: Path::new(TESTDATA_PATH),
-                patterns: &["OWNERS"],
-                case_sensitive: true,
-            })
-            .unwrap(),
-            None
-        ));
+        assert!(detect(Globs {
+            root: Path::new(TESTDATA_PATH),
+            patterns: &["OWNERS"],
+            case_sensitive: true,
+        })
+        .unwrap()
+        .is_none());
     }
 
     #[test]
     fn detect_file_not_located() {
-        assert!(matches!(
-            detect(Globs {
-                root: Path::new(TESTDATA_PATH),
-                patterns: &["nonexisting"],
-                case_sensitive: true,
-            })
-            .unwrap(),
-            None
-        ));
+        assert!(detect(Globs {
+            root: Path::new(TESTDATA_PATH),
+            patterns: &["nonexisting"],
+            case_sensitive: true,
+        })
+        .unwrap()
+        .is_none());
     }
 
     #[test]
     fn detect_invalid_glob_pattern() {
-        assert!(matches!(
-            detect(Globs {
-                root: Path::new(TESTDATA_PATH),
-                patterns: &["invalid***"],
-                case_sensitive: true,
-            }),
-            Err(_)
-        ));
+        assert!(detect(Globs {
+            root: Path::new(TESTDATA_PATH),
+            patterns: &["invalid***"],
+            case_sensitive: true,
+        })
+        .is_err());
     }
 }
@@ -1,3 +1,4 @@
+use super::custom::CustomCheck;
 use anyhow::Error;
 use serde::Deserialize;
 use std::ffi::OsStr;
@@ -13,6 +14,7 @@ pub const METADATA_FILE: &str = ".clomonitor.yml";
 pub struct Metadata {
     pub exemptions: Option<Vec<Exemption>>,
     pub license_scanning: Option<LicenseScanning>,
+    pub checks: Option<Vec<CustomCheck>>,
 }
 
 impl Metadata {
@@ -43,6 +45,7 @@ pub struct LicenseScanning {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::linter::{CustomRule, Section};
 
     const TESTDATA_PATH: &str = "src/linter/check/testdata";
 
@@ -59,7 +62,20 @@ mod tests {
                 exemptions: Some(vec![Exemption {
                     check: "artifacthub_badge".to_string(),
                     reason: "this is a sample reason".to_string(),
-                }])
+                }]),
+                checks: Some(vec![CustomCheck {
+                    id: "security_contacts".to_string(),
+                    name: Some("Security contacts".to_string()),
+                    section: Section::Security,
+                    weight: 2,
+                    failure_message: "security contacts file not found".to_string(),
+                    remediation: None,
+                    docs_url: None,
+                    check_sets: None,
+                    rule: CustomRule::File {
+                        patterns: vec!["SECURITY_CONTACTS*".to_string()],
+                    },
+                }]),
             },
         );
     }
@@ -74,9 +90,6 @@ mod tests {
 
     #[test]
     fn metadata_from_path_invalid_metadata_file() {
-        assert!(matches!(
-            Metadata::from(Path::new(TESTDATA_PATH).join("invalid")),
-            Err(_)
-        ));
+        assert!(Metadata::from(Path::new(TESTDATA_PATH).join("invalid")).is_err());
     }
 }
@@ -1,37 +1,47 @@
 use self::path::Globs;
-use crate::{config::*, linter::CheckSet};
+use crate::{
+    config::*,
+    linter::{CheckSet, Report, Section},
+};
 use anyhow::Error;
-use metadata::{Exemption, Metadata};
-use octocrab::models::Repository;
+use async_trait::async_trait;
+use metadata::{Exemption, Metadata, METADATA_FILE};
 use patterns::*;
+use provider::{Provider, RepositoryMetadata};
 use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
-use std::{
-    future::Future,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
+pub(crate) mod catalog;
 pub(crate) mod content;
+pub(crate) mod custom;
 pub(crate) mod git;
 pub(crate) mod github;
+pub(crate) mod gitlab;
 pub(crate) mod license;
 pub(crate) mod metadata;
 pub(crate) mod path;
 pub(crate) mod patterns;
+pub(crate) mod provider;
 
 /// Information used by checks to perform their operations.
 #[derive(Debug)]
 #[non_exhaustive]
-pub(crate) struct CheckOptions {
+pub struct CheckOptions {
     pub check_sets: Vec<CheckSet>,
     pub root: PathBuf,
     pub url: String,
+    pub offline: bool,
     pub md: Option<Metadata>,
-    pub gh_md: Repository,
+    pub repo_md: RepositoryMetadata,
+    pub provider: Box<dyn Provider>,
+
+    /// License's SPDX id detected by the SPDX id check, when it was run.
+    pub spdx_id: Option<String>,
 }
 
 /// Check result information.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct CheckResult<T = ()> {
     pub passed: bool,
@@ -46,6 +56,21 @@ pub struct CheckResult<T = ()> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exemption_reason: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_message: Option<String>,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub not_evaluated: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<CheckDetails>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<Evidence>,
 }
 
 impl<T> Default for CheckResult<T> {
@@ -56,6 +81,11 @@ impl<T> Default for CheckResult<T> {
             value: None,
             exempt: false,
             exemption_reason: None,
+            failure_message: None,
+            not_evaluated: false,
+            error: None,
+            details: None,
+            evidence: None,
         }
     }
 }
@@ -79,6 +109,15 @@ impl<T> From<Option<T>> for CheckResult<T> {
     }
 }
 
+impl<T> From<Error> for CheckResult<T> {
+    fn from(err: Error) -> Self {
+        Self {
+            error: Some(format!("{err:#}")),
+            ..Default::default()
+        }
+    }
+}
+
 impl<T> From<Exemption> for CheckResult<T> {
     fn from(exemption: Exemption) -> Self {
         Self {
@@ -99,132 +138,439 @@ impl<T> CheckResult<T> {
         }
     }
 
-    /// Create a new CheckResult instance from the Github url built using the
-    /// path provided.
-    pub(crate) fn from_path(path: Option<PathBuf>, gh_md: &Repository) -> Self {
+    /// Create a new CheckResult instance for a check that could not be
+    /// evaluated (i.e. a remote check when linting in offline mode).
+    pub(crate) fn new_not_evaluated() -> Self {
+        Self {
+            not_evaluated: true,
+            ..Default::default()
+        }
+    }
+
+    /// Create a new CheckResult instance from the git hosting provider url
+    /// built using the path provided.
+    pub(crate) fn from_path(path: Option<PathBuf>, opts: &CheckOptions) -> Self {
         match path {
             Some(path) => {
-                let url = github::build_url(
-                    &path,
-                    &gh_md.owner.as_ref().unwrap().login,
-                    &gh_md.name,
-                    gh_md.default_branch.as_ref().unwrap(),
-                );
+                let url = opts.provider.file_url(&path, &opts.repo_md);
                 CheckResult::from_url(Some(url))
             }
             None => false.into(),
         }
     }
+
+    /// Create a new passed CheckResult instance including the evidence of the
+    /// content match provided.
+    pub(crate) fn from_match(m: content::Match, opts: &CheckOptions) -> Self {
+        let url = format!(
+            "{}#L{}",
+            opts.provider.file_url(&m.path, &opts.repo_md),
+            m.line
+        );
+        Self {
+            passed: true,
+            evidence: Some(Evidence {
+                file: m.path.to_string_lossy().into_owned(),
+                line: m.line,
+                snippet: m.snippet,
+                url,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new failed CheckResult instance including the details about
+    /// where the check looked for the information provided.
+    pub(crate) fn failed(details: CheckDetails) -> Self {
+        Self {
+            details: Some(details),
+            ..Default::default()
+        }
+    }
+
+    /// Return a mutable reference to the result details, initializing them
+    /// if needed.
+    pub(crate) fn details_mut(&mut self) -> &mut CheckDetails {
+        self.details.get_or_insert_with(CheckDetails::default)
+    }
+
+    /// Convert this result into a result holding a different value type,
+    /// using the function provided to map the current value (if any).
+    pub(crate) fn map_value<U, F>(self, f: F) -> CheckResult<U>
+    where
+        F: FnOnce(T) -> Option<U>,
+    {
+        CheckResult {
+            passed: self.passed,
+            url: self.url,
+            value: self.value.and_then(f),
+            exempt: self.exempt,
+            exemption_reason: self.exemption_reason,
+            failure_message: self.failure_message,
+            not_evaluated: self.not_evaluated,
+            error: self.error,
+            details: self.details,
+            evidence: self.evidence,
+        }
+    }
 }
 
-/// Wrapper function that takes care of running some common pre-check
-/// operations and the synchronous check function provided.
-pub(crate) fn run_check<T, F>(
-    check_id: &str,
-    check_fn: F,
-    opts: &CheckOptions,
-) -> Result<Option<CheckResult<T>>, Error>
-where
-    F: Fn(&CheckOptions) -> Result<CheckResult<T>, Error>,
-{
-    if should_skip_check(check_id, &opts.check_sets) {
-        return Ok(None);
+/// Explanation of how a check was performed, attached to failed results to
+/// help understanding why they failed and how to fix them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CheckDetails {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub globs: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub readme_regexps: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub community_repo_checked: bool,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+}
+
+impl CheckDetails {
+    /// Create a new CheckDetails instance from the file globs tried.
+    pub(crate) fn from_globs(patterns: &[&str]) -> Self {
+        Self {
+            globs: patterns.iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        }
     }
 
-    // Check if an exemption has been declared for this check
-    if let Some(exemption) = find_exemption(check_id, &opts.md) {
-        return Ok(Some(exemption.into()));
+    /// Create a new CheckDetails instance from the README regular expressions
+    /// evaluated.
+    pub(crate) fn from_readme_regexps<S: AsRef<str>>(regexps: &[S]) -> Self {
+        Self {
+            readme_regexps: regexps.iter().map(|re| re.as_ref().to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Add the other source provided to the list of sources consulted.
+    pub(crate) fn add_source(&mut self, source: &str) {
+        self.sources.push(source.to_string());
+    }
+}
+
+/// Evidence of the content that made a check pass.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Evidence {
+    pub file: String,
+    pub line: usize,
+    pub snippet: String,
+    pub url: String,
+}
+
+/// Value that a check can attach to its result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CheckValue {
+    Bool(bool),
+    String(String),
+}
+
+/// Type erased check result, as returned by the checks in the registry.
+pub type CheckOutput = CheckResult<CheckValue>;
+
+impl From<CheckResult> for CheckOutput {
+    fn from(r: CheckResult) -> Self {
+        r.map_value(|_| None)
+    }
+}
+
+impl From<CheckResult<bool>> for CheckOutput {
+    fn from(r: CheckResult<bool>) -> Self {
+        r.map_value(|v| Some(CheckValue::Bool(v)))
+    }
+}
+
+impl From<CheckResult<String>> for CheckOutput {
+    fn from(r: CheckResult<String>) -> Self {
+        r.map_value(|v| Some(CheckValue::String(v)))
+    }
+}
+
+impl From<CheckOutput> for CheckResult {
+    fn from(r: CheckOutput) -> Self {
+        r.map_value(|_| None)
+    }
+}
+
+impl From<CheckOutput> for CheckResult<bool> {
+    fn from(r: CheckOutput) -> Self {
+        r.map_value(|v| match v {
+            CheckValue::Bool(v) => Some(v),
+            CheckValue::String(_) => None,
+        })
+    }
+}
+
+impl From<CheckOutput> for CheckResult<String> {
+    fn from(r: CheckOutput) -> Self {
+        r.map_value(|v| match v {
+            CheckValue::String(v) => Some(v),
+            CheckValue::Bool(_) => None,
+        })
+    }
+}
+
+/// Trait implemented by all checks the linter can run.
+///
+/// Checks are registered in a [`Registry`](crate::linter::Registry), which is
+/// used by the linter to run them and by the score calculation to weigh their
+/// results. Synchronous checks can implement `run` without awaiting anything.
+#[async_trait]
+pub trait Check: Send + Sync {
+    /// Check identifier (used in the report and in exemptions).
+    fn id(&self) -> &str;
+
+    /// Human readable name of the check.
+    fn name(&self) -> &str;
+
+    /// Report section the check belongs to.
+    fn section(&self) -> Section;
+
+    /// Weight of the check when calculating the section score.
+    fn weight(&self) -> usize;
+
+    /// Check sets this check is part of.
+    fn check_sets(&self) -> Vec<CheckSet>;
+
+    /// Hint about how to make the check pass, included in failed results.
+    fn remediation(&self) -> Option<&str> {
+        None
+    }
+
+    /// Url of the check's documentation, included in failed results.
+    fn docs_url(&self) -> Option<String> {
+        None
     }
 
-    // Call sync check function and wrap returned check result in an option
-    check_fn(opts).map(Some)
+    /// Whether the check can only be evaluated using information provided by
+    /// remote services. These checks are not evaluated in offline mode.
+    fn remote_only(&self) -> bool {
+        false
+    }
+
+    /// Run the check.
+    async fn run(&self, opts: &CheckOptions) -> Result<CheckOutput, Error>;
 }
 
 /// Wrapper function that takes care of running some common pre-check
-/// operations and the asynchronous check function provided.
-pub(crate) async fn run_async_check<'a, T, F, Fut>(
-    check_id: &str,
-    check_async_fn: F,
-    opts: &'a CheckOptions,
-) -> Result<Option<CheckResult<T>>, Error>
-where
-    F: Fn(&'a CheckOptions) -> Fut,
-    Fut: Future<Output = Result<CheckResult<T>, Error>>,
-{
-    if should_skip_check(check_id, &opts.check_sets) {
-        return Ok(None);
+/// operations and the check provided. Errors returned by the check are
+/// recorded in its result, so they don't affect the other checks.
+pub(crate) async fn run_check(check: &dyn Check, opts: &CheckOptions) -> Option<CheckOutput> {
+    if should_skip_check(check, &opts.check_sets) {
+        return None;
     }
 
     // Check if an exemption has been declared for this check
-    if let Some(exemption) = find_exemption(check_id, &opts.md) {
-        return Ok(Some(exemption.into()));
+    if let Some(exemption) = find_exemption(check.id(), &opts.md) {
+        return Some(exemption.into());
+    }
+
+    // Remote only checks cannot be evaluated in offline mode
+    if opts.offline && check.remote_only() {
+        return Some(CheckResult::new_not_evaluated());
     }
 
-    // Call async check function and wrap returned check result in an option
-    check_async_fn(opts).await.map(Some)
+    // Run check and wrap returned check result in an option
+    let mut output: CheckOutput = match check.run(opts).await {
+        Ok(output) => output,
+        Err(err) => return Some(err.into()),
+    };
+
+    // Add remediation information to failed checks results
+    if !output.passed {
+        let details = output.details_mut();
+        details.remediation = check.remediation().map(ToString::to_string);
+        details.docs_url = check.docs_url();
+    }
+
+    Some(output)
+}
+
+/// Built-in checks that rely exclusively on remote information.
+const REMOTE_ONLY_CHECKS: [&str; 3] = [RECENT_RELEASE, TRADEMARK_DISCLAIMER, WEBSITE];
+
+/// Declare the built-in checks, backed by the check functions defined in this
+/// module, along with the report field their results are stored in. Built-in
+/// checks take their weight and check sets from the configuration. The order
+/// in which they are declared is the order in which they are displayed and
+/// scored.
+macro_rules! builtin_checks {
+    ($($type:ident: $id:ident, $name:literal, $section:ident($report_section:ident.$field:ident), $fn:ident $(.$await:ident)?;)*) => {
+        $(
+            pub(crate) struct $type;
+
+            #[async_trait]
+            impl Check for $type {
+                fn id(&self) -> &str {
+                    $id
+                }
+
+                fn name(&self) -> &str {
+                    $name
+                }
+
+                fn section(&self) -> Section {
+                    Section::$section
+                }
+
+                fn weight(&self) -> usize {
+                    CHECK_WEIGHT[$id]
+                }
+
+                fn check_sets(&self) -> Vec<CheckSet> {
+                    check_sets_for($id)
+                }
+
+                fn remediation(&self) -> Option<&str> {
+                    Some(CHECK_REMEDIATION[$id])
+                }
+
+                fn docs_url(&self) -> Option<String> {
+                    Some(format!("{CHECKS_DOCS_URL}#{}", CHECK_DOCS_ANCHOR[$id]))
+                }
+
+                fn remote_only(&self) -> bool {
+                    REMOTE_ONLY_CHECKS.contains(&$id)
+                }
+
+                async fn run(&self, opts: &CheckOptions) -> Result<CheckOutput, Error> {
+                    $fn(opts)$(.$await)?.map(Into::into)
+                }
+            }
+        )*
+
+        /// Return all built-in checks.
+        pub(crate) fn builtin_checks() -> Vec<Box<dyn Check>> {
+            vec![$(Box::new($type)),*]
+        }
+
+        /// Id, section and report field name of each of the built-in checks.
+        pub(crate) const BUILTIN_FIELDS: &[(&str, Section, &str)] = &[
+            $(($id, Section::$section, stringify!($field))),*
+        ];
+
+        /// Return the result of the built-in check provided stored in the
+        /// report. None is returned if the check is not a built-in one.
+        pub(crate) fn get_builtin_result(report: &Report, check_id: &str) -> Option<Option<CheckOutput>> {
+            match check_id {
+                $($id => Some(report.$report_section.$field.clone().map(Into::into)),)*
+                _ => None,
+            }
+        }
+
+        /// Store the result of the built-in check provided in the report. The
+        /// output is given back if the check is not a built-in one.
+        pub(crate) fn set_builtin_result(report: &mut Report, check_id: &str, output: CheckOutput) -> Option<CheckOutput> {
+            match check_id {
+                $($id => report.$report_section.$field = Some(output.into()),)*
+                _ => return Some(output),
+            }
+            None
+        }
+    };
+}
+
+builtin_checks! {
+    Adopters: ADOPTERS, "Adopters", Documentation(documentation.adopters), adopters;
+    Changelog: CHANGELOG, "Changelog", Documentation(documentation.changelog), changelog.await;
+    CodeOfConduct: CODE_OF_CONDUCT, "Code of conduct", Documentation(documentation.code_of_conduct), code_of_conduct.await;
+    Contributing: CONTRIBUTING, "Contributing", Documentation(documentation.contributing), contributing.await;
+    Governance: GOVERNANCE, "Governance", Documentation(documentation.governance), governance;
+    Maintainers: MAINTAINERS, "Maintainers", Documentation(documentation.maintainers), maintainers;
+    Readme: README, "Readme", Documentation(documentation.readme), readme;
+    Roadmap: ROADMAP, "Roadmap", Documentation(documentation.roadmap), roadmap;
+    Website: WEBSITE, "Website", Documentation(documentation.website), website;
+    LicenseSpdx: LICENSE_SPDX, "SPDX id", License(license.spdx_id), license;
+    LicenseApproved: LICENSE_APPROVED, "Approved", License(license.approved), license_approved;
+    LicenseScanning: LICENSE_SCANNING, "Scanning", License(license.scanning), license_scanning;
+    ArtifactHubBadge: ARTIFACTHUB_BADGE, "Artifact Hub badge", BestPractices(best_practices.artifacthub_badge), artifacthub_badge;
+    CommunityMeeting: COMMUNITY_MEETING, "Community meeting", BestPractices(best_practices.community_meeting), community_meeting;
+    Dco: DCO, "DCO", BestPractices(best_practices.dco), dco.await;
+    OpenssfBadge: OPENSSF_BADGE, "OpenSSF (CII) badge", BestPractices(best_practices.openssf_badge), openssf_badge;
+    RecentRelease: RECENT_RELEASE, "Recent release", BestPractices(best_practices.recent_release), recent_release.await;
+    SlackPresence: SLACK_PRESENCE, "Slack presence", BestPractices(best_practices.slack_presence), slack_presence;
+    Sbom: SBOM, "SBOM", Security(security.sbom), sbom.await;
+    SecurityPolicy: SECURITY_POLICY, "Security policy", Security(security.security_policy), security_policy.await;
+    TrademarkDisclaimer: TRADEMARK_DISCLAIMER, "Trademark disclaimer", Legal(legal.trademark_disclaimer), trademark_disclaimer.await;
 }
 
 /// Adopters check.
 pub(crate) fn adopters(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    find_file_or_reference(opts, &ADOPTERS_FILE, &*ADOPTERS_IN_README)
+    find_file_or_reference(opts, &ADOPTERS_FILE, &ADOPTERS_IN_README)
 }
 
 /// Artifact Hub badge check.
 pub(crate) fn artifacthub_badge(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // Reference in README file
-    Ok(CheckResult::from_url(readme_capture(
-        &opts.root,
-        vec![&*ARTIFACTHUB_URL],
-    )?))
+    readme_capture_url(opts, vec![&ARTIFACTHUB_URL])
 }
 
 /// Changelog check.
 pub(crate) async fn changelog(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    let r = find_file_or_reference(opts, &CHANGELOG_FILE, &*CHANGELOG_IN_README)?;
+    let mut r = find_file_or_reference(opts, &CHANGELOG_FILE, &CHANGELOG_IN_README)?;
     if r.passed {
         return Ok(r);
     }
 
     // Reference in last release
-    if github::last_release_body_matches(&opts.url, &*CHANGELOG_IN_GH_RELEASE).await? {
-        return Ok(true.into());
+    if !opts.offline {
+        if provider::last_release_body_matches(opts.provider.as_ref(), &CHANGELOG_IN_GH_RELEASE)
+            .await?
+        {
+            return Ok(true.into());
+        }
+        r.details_mut().add_source("last release notes");
     }
 
-    Ok(false.into())
+    Ok(r)
 }
 
 /// Code of conduct check.
 pub(crate) async fn code_of_conduct(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    let r = find_file_or_reference(opts, &CODE_OF_CONDUCT_FILE, &*CODE_OF_CONDUCT_IN_README)?;
+    let r = find_file_or_reference(opts, &CODE_OF_CONDUCT_FILE, &CODE_OF_CONDUCT_IN_README)?;
     if r.passed {
         return Ok(r);
     }
 
     // File in .github repo
-    let url = github::has_community_health_file("CODE_OF_CONDUCT.md", &opts.gh_md).await?;
-    Ok(CheckResult::from_url(url))
+    community_health_file(opts, r, "CODE_OF_CONDUCT.md").await
 }
 
 /// Community meeting check.
 pub(crate) fn community_meeting(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // Reference in README file
-    Ok(readme_matches(&opts.root, &*COMMUNITY_MEETING_TEXT)?.into())
+    readme_reference(opts, &COMMUNITY_MEETING_TEXT)
 }
 
 /// Contributing check.
 pub(crate) async fn contributing(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    let r = find_file_or_reference(opts, &CONTRIBUTING_FILE, &*CONTRIBUTING_IN_README)?;
+    let r = find_file_or_reference(opts, &CONTRIBUTING_FILE, &CONTRIBUTING_IN_README)?;
     if r.passed {
         return Ok(r);
     }
 
     // File in .github repo
-    let url = github::has_community_health_file("CONTRIBUTING.md", &opts.gh_md).await?;
-    Ok(CheckResult::from_url(url))
+    community_health_file(opts, r, "CONTRIBUTING.md").await
 }
 
 /// Developer Certificate of Origin check.
@@ -237,17 +583,22 @@ pub(crate) async fn dco(opts: &CheckOptions) -> Result<CheckResult, Error> {
     }
 
     // DCO app reference in last closed PR
-    if github::last_pr_has_dco_check(&opts.url).await? {
-        return Ok(true.into());
+    let mut details = CheckDetails::default();
+    details.add_source("last commits signatures");
+    if !opts.offline {
+        if opts.provider.last_pr_has_dco_check().await? {
+            return Ok(true.into());
+        }
+        details.add_source("last closed pull request checks");
     }
 
-    Ok(false.into())
+    Ok(CheckResult::failed(details))
 }
 
 /// Governance check.
 pub(crate) fn governance(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    find_file_or_reference(opts, &GOVERNANCE_FILE, &*GOVERNANCE_IN_README)
+    find_file_or_reference(opts, &GOVERNANCE_FILE, &GOVERNANCE_IN_README)
 }
 
 /// License check.
@@ -261,41 +612,31 @@ pub(crate) fn license(opts: &CheckOptions) -> Result<CheckResult<String>, Error>
         return Ok(Some(spdx_id).into());
     }
 
-    // License detected by Github
-    if let Some(license) = &opts.gh_md.license {
-        if license.spdx_id != "NOASSERTION" {
-            return Ok(Some(license.spdx_id.to_owned()).into());
-        }
+    // License detected by the git hosting provider
+    if let Some(spdx_id) = &opts.repo_md.license_spdx_id {
+        return Ok(Some(spdx_id.to_owned()).into());
     }
 
-    Ok(false.into())
+    let mut details = CheckDetails::from_globs(&LICENSE_FILE);
+    if !opts.offline {
+        details.add_source("repository metadata");
+    }
+    Ok(CheckResult::failed(details))
 }
 
 /// Approved license check.
-pub(crate) fn license_approved(
-    spdx_id: &Option<String>,
-    opts: &CheckOptions,
-) -> Result<Option<CheckResult<bool>>, Error> {
-    if should_skip_check(LICENSE_APPROVED, &opts.check_sets) {
-        return Ok(None);
-    }
-
-    // Check if an exemption has been declared for this check
-    if let Some(exemption) = find_exemption(LICENSE_APPROVED, &opts.md) {
-        return Ok(Some(exemption.into()));
-    }
-
-    // SPDX id in list of approved licenses
-    let mut approved: Option<bool> = None;
-    if let Some(spdx_id) = &spdx_id {
-        approved = Some(license::is_approved(spdx_id))
-    }
-
-    Ok(Some(CheckResult {
+pub(crate) fn license_approved(opts: &CheckOptions) -> Result<CheckResult<bool>, Error> {
+    // SPDX id detected in list of approved licenses
+    let approved = opts
+        .spdx_id
+        .as_ref()
+        .map(|spdx_id| license::is_approved(spdx_id));
+
+    Ok(CheckResult {
         passed: approved.unwrap_or(false),
         value: approved,
         ..Default::default()
-    }))
+    })
 }
 
 /// License scanning check.
@@ -310,123 +651,135 @@ pub(crate) fn license_scanning(opts: &CheckOptions) -> Result<CheckResult, Error
     }
 
     // Reference in README file
-    if let Some(url) = content::find(readme_globs(&opts.root), vec![&*FOSSA_URL, &*SNYK_URL])? {
-        return Ok(CheckResult::from_url(Some(url)));
-    };
-
-    Ok(false.into())
+    let mut r = readme_capture_url(opts, vec![&FOSSA_URL, &SNYK_URL])?;
+    if !r.passed {
+        r.details_mut().add_source(METADATA_FILE);
+    }
+    Ok(r)
 }
 
 /// Maintainers check.
 pub(crate) fn maintainers(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    find_file_or_reference(opts, &MAINTAINERS_FILE, &*MAINTAINERS_IN_README)
+    find_file_or_reference(opts, &MAINTAINERS_FILE, &MAINTAINERS_IN_README)
 }
 
 /// OpenSSF badge check.
 pub(crate) fn openssf_badge(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // Reference in README file
-    Ok(CheckResult::from_url(readme_capture(
-        &opts.root,
-        vec![&*OPENSSF_URL],
-    )?))
+    readme_capture_url(opts, vec![&OPENSSF_URL])
 }
 
 /// Recent release check.
 pub(crate) async fn recent_release(opts: &CheckOptions) -> Result<CheckResult, Error> {
-    Ok(CheckResult::from_url(
-        github::has_recent_release(&opts.url).await?,
-    ))
+    if let Some(url) = provider::has_recent_release(opts.provider.as_ref()).await? {
+        return Ok(CheckResult::from_url(Some(url)));
+    }
+
+    let mut details = CheckDetails::default();
+    details.add_source("last release");
+    Ok(CheckResult::failed(details))
 }
 
 /// Roadmap check.
 pub(crate) fn roadmap(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README
-    find_file_or_reference(opts, &ROADMAP_FILE, &*ROADMAP_IN_README)
+    find_file_or_reference(opts, &ROADMAP_FILE, &ROADMAP_IN_README)
 }
 
 /// Readme check.
 pub(crate) fn readme(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo
     if let Some(path) = path::find(readme_globs(&opts.root))? {
-        return Ok(CheckResult::from_path(Some(path), &opts.gh_md));
+        return Ok(CheckResult::from_path(Some(path), opts));
     }
 
-    Ok(false.into())
+    Ok(CheckResult::failed(CheckDetails::from_globs(&README_FILE)))
 }
 
 /// Software bill of materials (SBOM).
 pub(crate) async fn sbom(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // Asset in last release
-    if let Some(last_release) = github::last_release(&opts.url).await? {
-        if last_release
-            .assets
-            .iter()
-            .any(|asset| SBOM_IN_GH_RELEASE.is_match(&asset.name))
-        {
-            return Ok(true.into());
+    if !opts.offline {
+        if let Some(last_release) = opts.provider.last_release().await? {
+            if last_release
+                .assets
+                .iter()
+                .any(|asset| SBOM_IN_GH_RELEASE.is_match(asset))
+            {
+                return Ok(true.into());
+            }
         }
     }
 
     // Reference in README file
-    Ok(readme_matches(&opts.root, &*SBOM_IN_README)?.into())
+    let mut r = readme_reference(opts, &SBOM_IN_README)?;
+    if !r.passed && !opts.offline {
+        r.details_mut().add_source("last release assets");
+    }
+    Ok(r)
 }
 
 /// Security policy check.
 pub(crate) async fn security_policy(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // File in repo or reference in README file
-    let r = find_file_or_reference(opts, &SECURITY_POLICY_FILE, &*SECURITY_POLICY_IN_README)?;
+    let r = find_file_or_reference(opts, &SECURITY_POLICY_FILE, &SECURITY_POLICY_IN_README)?;
     if r.passed {
         return Ok(r);
     }
 
     // File in .github repo
-    let url = github::has_community_health_file("SECURITY.md", &opts.gh_md).await?;
-    Ok(CheckResult::from_url(url))
+    community_health_file(opts, r, "SECURITY.md").await
 }
 
 /// Slack presence check.
 pub(crate) fn slack_presence(opts: &CheckOptions) -> Result<CheckResult, Error> {
     // Reference in README file
-    Ok(readme_matches(&opts.root, &*SLACK_IN_README)?.into())
+    readme_reference(opts, &SLACK_IN_README)
 }
 
 /// Trademark disclaimer check.
 pub(crate) async fn trademark_disclaimer(opts: &CheckOptions) -> Result<CheckResult, Error> {
-    // Trademark disclaimer in website setup in Github
-    if let Some(url) = &opts.gh_md.homepage {
-        if !url.is_empty() {
-            return Ok(
-                content::remote_matches(url, &*TRADEMARK_DISCLAIMER_IN_WEBSITE)
-                    .await?
-                    .into(),
-            );
+    // Trademark disclaimer in website setup in the git hosting provider
+    let mut details = CheckDetails::default();
+    if let Some(url) = &opts.repo_md.homepage {
+        if content::remote_matches(url, &TRADEMARK_DISCLAIMER_IN_WEBSITE).await? {
+            return Ok(true.into());
         }
+        details.add_source(url);
+    } else {
+        details.add_source("repository metadata");
     }
 
-    Ok(false.into())
+    Ok(CheckResult::failed(details))
 }
 
 /// Website check.
 pub(crate) fn website(opts: &CheckOptions) -> Result<CheckResult, Error> {
-    // Website in Github
-    if let Some(url) = &opts.gh_md.homepage {
-        if !url.is_empty() {
-            return Ok(CheckResult::from_url(Some(url.to_string())));
-        }
+    // Website in the git hosting provider
+    if let Some(url) = &opts.repo_md.homepage {
+        return Ok(CheckResult::from_url(Some(url.to_string())));
     }
 
-    Ok(false.into())
+    let mut details = CheckDetails::default();
+    details.add_source("repository metadata");
+    Ok(CheckResult::failed(details))
 }
 
 /// Check if the check provided should be skipped.
-fn should_skip_check(check_id: &str, check_sets: &[CheckSet]) -> bool {
+fn should_skip_check(check: &dyn Check, check_sets: &[CheckSet]) -> bool {
     // Skip if the check doesn't belong to any of the check sets provided
-    if !check_sets.iter().any(|k| CHECKSET[k].contains(&check_id)) {
-        return true;
-    }
+    let check_check_sets = check.check_sets();
+    !check_sets.iter().any(|k| check_check_sets.contains(k))
+}
 
-    false
+/// Return the check sets the built-in check provided belongs to.
+fn check_sets_for(check_id: &str) -> Vec<CheckSet> {
+    CHECKSET
+        .iter()
+        .filter(|(_, checks)| checks.contains(&check_id))
+        .map(|(check_set, _)| check_set.clone())
+        .collect()
 }
 
 /// Check if the repository is exempt from passing the provided check.
@@ -456,31 +809,83 @@ fn find_file_or_reference(
         patterns,
         case_sensitive: false,
     })? {
-        return Ok(CheckResult::from_path(Some(path), &opts.gh_md));
+        return Ok(CheckResult::from_path(Some(path), opts));
     }
 
     // Reference in README file
-    if readme_matches(&opts.root, re)? {
-        return Ok(true.into());
+    if let Some(m) = readme_matches(&opts.root, re)? {
+        return Ok(CheckResult::from_match(m, opts));
+    }
+
+    Ok(CheckResult::failed(CheckDetails {
+        globs: patterns.iter().map(ToString::to_string).collect(),
+        readme_regexps: re.patterns().to_vec(),
+        ..Default::default()
+    }))
+}
+
+/// Check if the file provided is available in the organization's community
+/// health files repository, when the result provided has not passed yet.
+async fn community_health_file(
+    opts: &CheckOptions,
+    mut r: CheckResult,
+    file: &str,
+) -> Result<CheckResult, Error> {
+    if opts.offline {
+        return Ok(r);
     }
+    if let Some(url) = opts.provider.community_health_file(file).await? {
+        return Ok(CheckResult::from_url(Some(url)));
+    }
+    r.details_mut().community_repo_checked = true;
+    Ok(r)
+}
 
-    Ok(false.into())
+/// Check if the README file content matches any of the regular expressions
+/// provided, returning a result that includes the regular expressions
+/// evaluated when it doesn't.
+fn readme_reference(opts: &CheckOptions, re: &RegexSet) -> Result<CheckResult, Error> {
+    if let Some(m) = readme_matches(&opts.root, re)? {
+        return Ok(CheckResult::from_match(m, opts));
+    }
+    Ok(CheckResult::failed(CheckDetails::from_readme_regexps(
+        re.patterns(),
+    )))
+}
+
+/// Check if the README file content matches any of the regular expressions
+/// provided, returning a result with the url captured when it does or one that
+/// includes the regular expressions evaluated when it doesn't.
+fn readme_capture_url(opts: &CheckOptions, regexps: Vec<&Regex>) -> Result<CheckResult, Error> {
+    let patterns: Vec<&str> = regexps.iter().map(|re| re.as_str()).collect();
+    match readme_capture(&opts.root, regexps)? {
+        Some(m) => {
+            let url = m.capture.clone();
+            Ok(CheckResult {
+                url,
+                ..CheckResult::from_match(m, opts)
+            })
+        }
+        None => Ok(CheckResult::failed(CheckDetails::from_readme_regexps(
+            &patterns,
+        ))),
+    }
 }
 
 /// Check if the README file content matches any of the regular expressions
-/// provided.
-fn readme_matches(root: &Path, re: &RegexSet) -> Result<bool, Error> {
+/// provided, returning the first match.
+fn readme_matches(root: &Path, re: &RegexSet) -> Result<Option<content::Match>, Error> {
     content::matches(readme_globs(root), re)
 }
 
 /// Check if the README file content matches any of the regular expressions
-/// provided, returning the value from the first capture group.
-fn readme_capture(root: &Path, regexps: Vec<&Regex>) -> Result<Option<String>, Error> {
+/// provided, returning the first match (including the value captured).
+fn readme_capture(root: &Path, regexps: Vec<&Regex>) -> Result<Option<content::Match>, Error> {
     content::find(readme_globs(root), regexps)
 }
 
 // Returns a Globs instance used to locate the README file.
-fn readme_globs(root: &Path) -> Globs {
+fn readme_globs(root: &Path) -> Globs<'_> {
     Globs {
         root,
         patterns: &README_FILE,
@@ -492,6 +897,107 @@ fn readme_globs(root: &Path) -> Globs {
 mod tests {
     use super::*;
 
+    const TESTDATA_PATH: &str = "src/linter/check/testdata";
+
+    fn offline_check_options() -> CheckOptions {
+        let url = "https://github.com/org/repo";
+        CheckOptions {
+            check_sets: vec![CheckSet::Code, CheckSet::Community],
+            root: PathBuf::from(TESTDATA_PATH),
+            url: url.to_string(),
+            offline: true,
+            md: None,
+            repo_md: RepositoryMetadata {
+                owner: "org".to_string(),
+                name: "repo".to_string(),
+                default_branch: "main".to_string(),
+                ..Default::default()
+            },
+            provider: provider::new(url, provider::Credentials::default()).unwrap(),
+            spdx_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn run_check_failed_includes_details() {
+        let r = run_check(&Contributing, &offline_check_options())
+            .await
+            .unwrap();
+        assert!(!r.passed);
+        assert_eq!(
+            r.details.unwrap(),
+            CheckDetails {
+                globs: CONTRIBUTING_FILE.iter().map(ToString::to_string).collect(),
+                readme_regexps: CONTRIBUTING_IN_README.patterns().to_vec(),
+                community_repo_checked: false,
+                sources: vec![],
+                remediation: Some(CHECK_REMEDIATION[CONTRIBUTING].to_string()),
+                docs_url: Some(format!("{CHECKS_DOCS_URL}#contributing")),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn run_check_readme_reference_includes_evidence() {
+        let r = run_check(&Adopters, &offline_check_options())
+            .await
+            .unwrap();
+        assert!(r.passed);
+        assert_eq!(
+            r.evidence.unwrap(),
+            Evidence {
+                file: "README.md".to_string(),
+                line: 7,
+                snippet: "## Adopters".to_string(),
+                url: "https://github.com/org/repo/blob/main/README.md#L7".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn run_check_passed_has_no_details() {
+        let r = run_check(&Maintainers, &offline_check_options())
+            .await
+            .unwrap();
+        assert!(r.passed);
+        assert!(r.details.is_none());
+    }
+
+    #[test]
+    fn license_approved_uses_spdx_id_detected() {
+        let mut opts = offline_check_options();
+        opts.spdx_id = Some("Apache-2.0".to_string());
+        let r = license_approved(&opts).unwrap();
+        assert!(r.passed);
+        assert_eq!(r.value, Some(true));
+
+        opts.spdx_id = Some("Proprietary".to_string());
+        let r = license_approved(&opts).unwrap();
+        assert!(!r.passed);
+        assert_eq!(r.value, Some(false));
+    }
+
+    #[test]
+    fn license_approved_does_not_detect_license_again() {
+        let r = license_approved(&offline_check_options()).unwrap();
+        assert!(!r.passed);
+        assert_eq!(r.value, None);
+    }
+
+    #[test]
+    fn builtin_results_are_stored_in_their_report_fields() {
+        let mut report = Report::default();
+        for check in builtin_checks() {
+            report.set(check.as_ref(), CheckOutput::from(true));
+            assert!(report.get(check.id()).unwrap().passed);
+        }
+        assert!(report.documentation.extra.is_empty());
+        assert!(report.license.extra.is_empty());
+        assert!(report.best_practices.extra.is_empty());
+        assert!(report.security.extra.is_empty());
+        assert!(report.legal.extra.is_empty());
+    }
+
     #[test]
     fn check_result_from_passed() {
         assert_eq!(
@@ -0,0 +1,200 @@
+use super::{github::GitHub, gitlab::GitLab};
+use anyhow::{format_err, Error};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use regex::RegexSet;
+use reqwest::Url;
+use std::{fmt::Debug, path::Path};
+
+/// Repository's metadata provided by the git hosting provider.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct RepositoryMetadata {
+    pub owner: String,
+    pub name: String,
+    pub default_branch: String,
+    pub homepage: Option<String>,
+    pub license_spdx_id: Option<String>,
+}
+
+/// Release information provided by the git hosting provider.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Release {
+    pub url: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub body: Option<String>,
+    pub assets: Vec<String>,
+}
+
+/// Trait that defines the operations a git hosting provider must support to
+/// be used by the linter.
+#[async_trait]
+pub trait Provider: Debug + Send + Sync {
+    /// Get repository's metadata.
+    async fn get_repo_metadata(&self) -> Result<RepositoryMetadata, Error>;
+
+    /// Return the last release of the repository when available.
+    async fn last_release(&self) -> Result<Option<Release>, Error>;
+
+    /// Check if the last closed pull (or merge) request in the repository
+    /// has the DCO check.
+    async fn last_pr_has_dco_check(&self) -> Result<bool, Error>;
+
+    /// Check if the given default community health file is available at the
+    /// organization level, returning the url to the file when found.
+    async fn community_health_file(&self, file: &str) -> Result<Option<String>, Error>;
+
+    /// Build the repository's metadata using only the information available
+    /// locally (used in offline mode).
+    fn local_repo_metadata(&self, default_branch: &str) -> RepositoryMetadata;
+
+    /// Build the url of the file located at the path provided.
+    fn file_url(&self, path: &Path, md: &RepositoryMetadata) -> String;
+}
+
+/// Credentials used to authenticate with the git hosting providers.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Credentials {
+    pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
+}
+
+/// Setup a provider for the repository url provided. The provider is selected
+/// based on the url's host.
+pub(crate) fn new(repo_url: &str, creds: Credentials) -> Result<Box<dyn Provider>, Error> {
+    let host = Url::parse(repo_url)
+        .map_err(|_| format_err!("invalid repository url"))?
+        .host_str()
+        .unwrap_or_default()
+        .to_lowercase();
+    if host == "github.com" {
+        Ok(Box::new(GitHub::new(repo_url, creds.github_token)?))
+    } else if host == "gitlab.com" || host.starts_with("gitlab.") {
+        Ok(Box::new(GitLab::new(repo_url, creds.gitlab_token)?))
+    } else {
+        Err(format_err!(
+            "unsupported git hosting provider: {host} (only GitHub and GitLab repositories, including self-hosted GitLab instances available at gitlab.* hosts, are supported)"
+        ))
+    }
+}
+
+/// Repository url components.
+#[derive(Debug, PartialEq)]
+pub(crate) struct RepositoryUrl {
+    pub base_url: String,
+    pub owner: String,
+    pub name: String,
+}
+
+impl RepositoryUrl {
+    /// Build the repository's metadata from the url components and the
+    /// default branch provided.
+    pub(crate) fn to_metadata(&self, default_branch: &str) -> RepositoryMetadata {
+        RepositoryMetadata {
+            owner: self.owner.clone(),
+            name: self.name.clone(),
+            default_branch: default_branch.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse the repository url provided. Owners can be nested (i.e. GitLab
+    /// subgroups) only when allowed.
+    pub(crate) fn parse(repo_url: &str, nested_owner: bool) -> Result<Self, Error> {
+        let invalid_url = || format_err!("invalid repository url");
+        let url = Url::parse(repo_url).map_err(|_| invalid_url())?;
+        let segments: Vec<&str> = url
+            .path_segments()
+            .ok_or_else(invalid_url)?
+            .filter(|s| !s.is_empty())
+            .collect();
+        if segments.len() < 2 || (!nested_owner && segments.len() > 2) {
+            return Err(invalid_url());
+        }
+        let (owner, name) = segments.split_at(segments.len() - 1);
+        Ok(Self {
+            base_url: url.origin().ascii_serialization(),
+            owner: owner.join("/"),
+            name: name[0].trim_end_matches(".git").to_string(),
+        })
+    }
+}
+
+/// Check if the repository has released a new version in the last year.
+pub(crate) async fn has_recent_release(provider: &dyn Provider) -> Result<Option<String>, Error> {
+    if let Some(last_release) = provider.last_release().await? {
+        if let Some(created_at) = last_release.created_at {
+            if created_at > Utc::now() - Duration::days(365) {
+                return Ok(Some(last_release.url));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Check if the last release body matches any of the regular expressions
+/// provided.
+pub(crate) async fn last_release_body_matches(
+    provider: &dyn Provider,
+    re: &RegexSet,
+) -> Result<bool, Error> {
+    if let Some(last_release) = provider.last_release().await? {
+        if let Some(body) = last_release.body {
+            return Ok(re.is_match(&body));
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_selects_provider_from_url() {
+        let creds = Credentials::default();
+        assert!(format!(
+            "{:?}",
+            new("https://github.com/org/repo", creds.clone()).unwrap()
+        )
+        .starts_with("GitHub"));
+        assert!(format!(
+            "{:?}",
+            new("https://gitlab.com/org/repo", creds.clone()).unwrap()
+        )
+        .starts_with("GitLab"));
+        assert!(new("https://example.com/org/repo", creds).is_err());
+    }
+
+    #[test]
+    fn parse_repository_url() {
+        assert_eq!(
+            RepositoryUrl::parse("https://github.com/org/repo/", false).unwrap(),
+            RepositoryUrl {
+                base_url: "https://github.com".to_string(),
+                owner: "org".to_string(),
+                name: "repo".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_repository_url_nested_owner() {
+        assert_eq!(
+            RepositoryUrl::parse("https://gitlab.com/group/subgroup/repo.git", true).unwrap(),
+            RepositoryUrl {
+                base_url: "https://gitlab.com".to_string(),
+                owner: "group/subgroup".to_string(),
+                name: "repo".to_string(),
+            }
+        );
+        assert!(RepositoryUrl::parse("https://github.com/group/subgroup/repo", false).is_err());
+    }
+
+    #[test]
+    fn parse_repository_url_invalid() {
+        assert!(RepositoryUrl::parse("https://github.com/org", false).is_err());
+        assert!(RepositoryUrl::parse("invalid", false).is_err());
+    }
+}
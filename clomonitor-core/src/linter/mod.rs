@@ -1,19 +1,28 @@
 use super::config::*;
 use anyhow::Error;
-use check::{
-    metadata::{Metadata, METADATA_FILE},
-    *,
-};
+use check::{metadata::Metadata, *};
 use clap::ArgEnum;
+use futures::future;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, fmt, path::PathBuf};
 
 mod check;
-pub use check::CheckResult;
+mod registry;
+pub use check::{
+    catalog::{catalog, CheckInfo, Criterion, Source},
+    custom::{CustomCheck, CustomRule},
+    metadata::METADATA_FILE,
+    provider::{Provider, Release, RepositoryMetadata},
+    Check, CheckDetails, CheckOptions, CheckOutput, CheckResult, CheckValue, Evidence,
+};
+pub use registry::Registry;
+
+/// Version of the linter, recorded along with the reports it produces.
+pub const LINTER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Check sets define a set of checks that will be run on a given repository.
 /// Multiple check sets can be assigned to a repository.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, ArgEnum, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ArgEnum, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum CheckSet {
     Code,
@@ -22,16 +31,52 @@ pub enum CheckSet {
     Docs,
 }
 
+/// Sections of the report checks can belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Section {
+    Documentation,
+    License,
+    BestPractices,
+    Security,
+    Legal,
+}
+
+impl Section {
+    /// All report sections, in the order they are displayed and scored.
+    pub const ALL: [Section; 5] = [
+        Section::Documentation,
+        Section::License,
+        Section::BestPractices,
+        Section::Security,
+        Section::Legal,
+    ];
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Section::Documentation => "Documentation",
+            Section::License => "License",
+            Section::BestPractices => "Best practices",
+            Section::Security => "Security",
+            Section::Legal => "Legal",
+        })
+    }
+}
+
 /// Linter configuration options.
 pub struct LintOptions {
     pub check_sets: Vec<CheckSet>,
     pub root: PathBuf,
     pub url: String,
+    pub offline: bool,
     pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
 }
 
 /// Linter report.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Report {
     pub documentation: Documentation,
@@ -42,7 +87,7 @@ pub struct Report {
 }
 
 /// Documentation section of the report.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Documentation {
     pub adopters: Option<CheckResult>,
@@ -54,19 +99,27 @@ pub struct Documentation {
     pub readme: Option<CheckResult>,
     pub roadmap: Option<CheckResult>,
     pub website: Option<CheckResult>,
+
+    /// Results of additional (non built-in) checks, keyed by check id.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, CheckOutput>,
 }
 
 /// License section of the report.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct License {
     pub approved: Option<CheckResult<bool>>,
     pub scanning: Option<CheckResult>,
     pub spdx_id: Option<CheckResult<String>>,
+
+    /// Results of additional (non built-in) checks, keyed by check id.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, CheckOutput>,
 }
 
 /// BestPractices section of the report.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct BestPractices {
     pub artifacthub_badge: Option<CheckResult>,
@@ -75,107 +128,140 @@ pub struct BestPractices {
     pub openssf_badge: Option<CheckResult>,
     pub recent_release: Option<CheckResult>,
     pub slack_presence: Option<CheckResult>,
+
+    /// Results of additional (non built-in) checks, keyed by check id.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, CheckOutput>,
 }
 
 /// Security section of the report.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Security {
     pub sbom: Option<CheckResult>,
     pub security_policy: Option<CheckResult>,
+
+    /// Results of additional (non built-in) checks, keyed by check id.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, CheckOutput>,
 }
 
 /// Legal section of the report.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Legal {
     pub trademark_disclaimer: Option<CheckResult>,
+
+    /// Results of additional (non built-in) checks, keyed by check id.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, CheckOutput>,
 }
 
-/// Lint the path provided and return a report.
-pub async fn lint(lint_opts: LintOptions) -> Result<Report, Error> {
-    // Initialize Github API client
-    let mut builder = octocrab::Octocrab::builder();
-    if let Some(token) = &lint_opts.github_token {
-        builder = builder.personal_token(token.to_string());
+impl Report {
+    /// Return the errors found running the checks registered, as pairs of
+    /// check id and error message.
+    pub fn errors(&self, registry: &Registry) -> Vec<(String, String)> {
+        registry
+            .iter()
+            .filter_map(|check| {
+                let error = self.get(check.id())?.error?;
+                Some((check.id().to_string(), error))
+            })
+            .collect()
+    }
+
+    /// Return the result of the check provided, if it was run.
+    pub fn get(&self, check_id: &str) -> Option<CheckOutput> {
+        if let Some(output) = check::get_builtin_result(self, check_id) {
+            return output;
+        }
+        [
+            &self.documentation.extra,
+            &self.license.extra,
+            &self.best_practices.extra,
+            &self.security.extra,
+            &self.legal.extra,
+        ]
+        .iter()
+        .find_map(|extra| extra.get(check_id).cloned())
     }
-    octocrab::initialise(builder)?;
+
+    /// Store the result of the check provided in the report.
+    pub fn set(&mut self, check: &dyn Check, output: CheckOutput) {
+        if let Some(output) = check::set_builtin_result(self, check.id(), output) {
+            let extra = match check.section() {
+                Section::Documentation => &mut self.documentation.extra,
+                Section::License => &mut self.license.extra,
+                Section::BestPractices => &mut self.best_practices.extra,
+                Section::Security => &mut self.security.extra,
+                Section::Legal => &mut self.legal.extra,
+            };
+            extra.insert(check.id().to_string(), output);
+        }
+    }
+}
+
+/// Lint the path provided and return a report.
+pub async fn lint(lint_opts: LintOptions, registry: &Registry) -> Result<Report, Error> {
+    // Setup git hosting provider
+    let provider = provider::new(
+        &lint_opts.url,
+        provider::Credentials {
+            github_token: lint_opts.github_token,
+            gitlab_token: lint_opts.gitlab_token,
+        },
+    )?;
 
     // Get CLOMonitor metadata
-    let md = Metadata::from(&lint_opts.root.join(METADATA_FILE))?;
+    let md = Metadata::from(lint_opts.root.join(METADATA_FILE))?;
 
-    // Get Github metadata
-    let gh_md = github::get_repo_metadata(&lint_opts.url).await?;
+    // Get repository metadata from the git hosting provider (or build it from
+    // the local information available when running in offline mode)
+    let repo_md = if lint_opts.offline {
+        let branch = git::current_branch(&lint_opts.root);
+        provider.local_repo_metadata(branch.as_deref().unwrap_or("HEAD"))
+    } else {
+        provider.get_repo_metadata().await?
+    };
 
     // Prepare check options
-    let check_opts = CheckOptions {
+    let mut check_opts = CheckOptions {
         check_sets: lint_opts.check_sets,
         root: lint_opts.root,
         url: lint_opts.url,
+        offline: lint_opts.offline,
         md,
-        gh_md,
+        repo_md,
+        provider,
+        spdx_id: None,
     };
 
-    // Async checks
-    let (
-        changelog,
-        code_of_conduct,
-        contributing,
-        dco,
-        recent_release,
-        sbom,
-        security_policy,
-        trademark_disclaimer,
-    ) = tokio::try_join!(
-        run_async_check(CHANGELOG, changelog, &check_opts),
-        run_async_check(CODE_OF_CONDUCT, code_of_conduct, &check_opts),
-        run_async_check(CONTRIBUTING, contributing, &check_opts),
-        run_async_check(DCO, dco, &check_opts),
-        run_async_check(RECENT_RELEASE, recent_release, &check_opts),
-        run_async_check(SBOM, sbom, &check_opts),
-        run_async_check(SECURITY_POLICY, security_policy, &check_opts),
-        run_async_check(TRADEMARK_DISCLAIMER, trademark_disclaimer, &check_opts),
-    )?;
+    // Run all registered checks concurrently, except the approved license
+    // one, which uses the license's SPDX id detected by the SPDX id check
+    let (approved_checks, checks): (Vec<&dyn Check>, Vec<&dyn Check>) = registry
+        .iter()
+        .partition(|check| check.id() == LICENSE_APPROVED);
+    let outputs = future::join_all(checks.iter().map(|check| run_check(*check, &check_opts))).await;
 
-    // Sync checks
-    let spdx_id = run_check(LICENSE_SPDX, license, &check_opts)?;
-    let mut spdx_id_value: &Option<String> = &None;
-    if let Some(r) = &spdx_id {
-        spdx_id_value = &r.value;
+    // Build report
+    let mut report = Report::default();
+    for (check, output) in checks.into_iter().zip(outputs) {
+        if let Some(output) = output {
+            report.set(check, output);
+        }
     }
 
-    // Build report and return it
-    Ok(Report {
-        documentation: Documentation {
-            adopters: run_check(ADOPTERS, adopters, &check_opts)?,
-            changelog,
-            code_of_conduct,
-            contributing,
-            governance: run_check(GOVERNANCE, governance, &check_opts)?,
-            maintainers: run_check(MAINTAINERS, maintainers, &check_opts)?,
-            readme: run_check(README, readme, &check_opts)?,
-            roadmap: run_check(ROADMAP, roadmap, &check_opts)?,
-            website: run_check(WEBSITE, website, &check_opts)?,
-        },
-        license: License {
-            approved: license_approved(spdx_id_value, &check_opts)?,
-            scanning: run_check(LICENSE_SCANNING, license_scanning, &check_opts)?,
-            spdx_id,
-        },
-        best_practices: BestPractices {
-            artifacthub_badge: run_check(ARTIFACTHUB_BADGE, artifacthub_badge, &check_opts)?,
-            community_meeting: run_check(COMMUNITY_MEETING, community_meeting, &check_opts)?,
-            dco,
-            openssf_badge: run_check(OPENSSF_BADGE, openssf_badge, &check_opts)?,
-            recent_release,
-            slack_presence: run_check(SLACK_PRESENCE, slack_presence, &check_opts)?,
-        },
-        security: Security {
-            sbom,
-            security_policy,
-        },
-        legal: Legal {
-            trademark_disclaimer,
-        },
-    })
+    // Run the approved license check
+    check_opts.spdx_id = report
+        .license
+        .spdx_id
+        .as_ref()
+        .and_then(|r| r.value.clone());
+    for check in approved_checks {
+        if let Some(output) = run_check(check, &check_opts).await {
+            report.set(check, output);
+        }
+    }
+
+    Ok(report)
 }
@@ -0,0 +1,171 @@
+use super::{check, check::metadata::Metadata, Check, Section};
+use anyhow::{format_err, Error};
+use std::path::Path;
+
+/// Registry of the checks the linter can run.
+///
+/// The linter runs all the checks registered, the score is calculated using
+/// their weights and sections, and the results are displayed in the same order
+/// the checks were registered. The default registry contains the built-in
+/// checks only.
+pub struct Registry {
+    checks: Vec<Box<dyn Check>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            checks: check::builtin_checks(),
+        }
+    }
+}
+
+impl Registry {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self { checks: Vec::new() }
+    }
+
+    /// Register the check provided. An error is returned if a check with the
+    /// same id has already been registered, or if its id matches the report
+    /// field of a different built-in check in the same section.
+    pub fn register(&mut self, check: Box<dyn Check>) -> Result<(), Error> {
+        if self.get(check.id()).is_some() {
+            return Err(format_err!("check {} already registered", check.id()));
+        }
+        if check::BUILTIN_FIELDS.iter().any(|(id, section, field)| {
+            *section == check.section() && *field == check.id() && *id != check.id()
+        }) {
+            return Err(format_err!(
+                "check {} conflicts with a built-in check field in the {} section",
+                check.id(),
+                check.section()
+            ));
+        }
+        self.checks.push(check);
+        Ok(())
+    }
+
+    /// Register the custom checks declared in the metadata file located at the
+    /// path provided. Nothing is registered if the file does not exist.
+    pub fn register_custom_checks<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Error> {
+        if let Some(md) = Metadata::from(path.as_ref())? {
+            for check in md.checks.unwrap_or_default() {
+                self.register(Box::new(check))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the check with the id provided, if it has been registered.
+    pub fn get(&self, check_id: &str) -> Option<&dyn Check> {
+        self.iter().find(|check| check.id() == check_id)
+    }
+
+    /// Return an iterator over all the checks registered.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Check> {
+        self.checks.iter().map(AsRef::as_ref)
+    }
+
+    /// Return an iterator over the checks registered in the section provided.
+    pub fn section(&self, section: Section) -> impl Iterator<Item = &dyn Check> {
+        self.iter().filter(move |check| check.section() == section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::*,
+        linter::{CheckOptions, CheckOutput, CheckSet, CustomCheck, CustomRule},
+    };
+    use async_trait::async_trait;
+
+    struct Custom;
+
+    #[async_trait]
+    impl Check for Custom {
+        fn id(&self) -> &str {
+            "custom"
+        }
+
+        fn name(&self) -> &str {
+            "Custom"
+        }
+
+        fn section(&self) -> Section {
+            Section::Security
+        }
+
+        fn weight(&self) -> usize {
+            1
+        }
+
+        fn check_sets(&self) -> Vec<CheckSet> {
+            vec![CheckSet::Code]
+        }
+
+        async fn run(&self, _opts: &CheckOptions) -> Result<CheckOutput, Error> {
+            Ok(true.into())
+        }
+    }
+
+    #[test]
+    fn default_registry_contains_builtin_checks() {
+        let registry = Registry::default();
+        assert_eq!(registry.iter().count(), CHECK_WEIGHT.len());
+        assert_eq!(registry.get(README).unwrap().weight(), CHECK_WEIGHT[README]);
+    }
+
+    #[test]
+    fn register_custom_check() {
+        let mut registry = Registry::default();
+        registry.register(Box::new(Custom)).unwrap();
+        assert!(registry.get("custom").is_some());
+        assert_eq!(
+            registry
+                .section(Section::Security)
+                .map(|check| check.id())
+                .collect::<Vec<&str>>(),
+            vec![SBOM, SECURITY_POLICY, "custom"]
+        );
+    }
+
+    #[test]
+    fn register_duplicated_check_fails() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Custom)).unwrap();
+        assert!(registry.register(Box::new(Custom)).is_err());
+    }
+
+    #[test]
+    fn register_check_conflicting_with_builtin_field_fails() {
+        let custom = |id: &str, section| {
+            Box::new(CustomCheck {
+                id: id.to_string(),
+                name: None,
+                section,
+                weight: 1,
+                failure_message: "failed".to_string(),
+                remediation: None,
+                docs_url: None,
+                check_sets: None,
+                rule: CustomRule::File { patterns: vec![] },
+            })
+        };
+        let mut registry = Registry::default();
+        for id in ["approved", "scanning", "spdx_id"] {
+            assert_eq!(
+                registry
+                    .register(custom(id, Section::License))
+                    .unwrap_err()
+                    .to_string(),
+                format!("check {id} conflicts with a built-in check field in the License section")
+            );
+        }
+        registry
+            .register(custom("scanning", Section::Security))
+            .unwrap();
+    }
+}
@@ -0,0 +1,302 @@
+use super::{calculate, should_score, Profile, RatingThresholds};
+use crate::linter::{Registry, Report, Section};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Margin used when comparing scores to absorb floating point rounding errors.
+const EPSILON: f64 = 1e-9;
+
+/// Explanation of how a score was calculated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Explanation {
+    pub global: f64,
+    pub global_weight: usize,
+    pub rating: char,
+    pub rating_thresholds: RatingThresholds,
+    pub checks: Vec<CheckContribution>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_rating: Option<NextRating>,
+}
+
+/// Contribution of a check to the global score, in points (0-100 scale).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckContribution {
+    pub check_id: String,
+    pub section: Section,
+    pub weight: usize,
+    pub passed: bool,
+    pub points: f64,
+    pub points_lost: f64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
+
+/// Smallest set of failed checks whose fix would reach the next rating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NextRating {
+    pub rating: char,
+    pub min_score: usize,
+    pub points_needed: f64,
+    pub fixes: Vec<CheckContribution>,
+}
+
+/// Explain the score of the linter report provided, detailing the points each
+/// check contributes to the global score (or loses when failed) and the fixes
+/// needed to reach the next rating.
+pub fn explain(report: &Report, registry: &Registry, profile: &Profile) -> Explanation {
+    let score = calculate(report, registry, profile);
+
+    let mut checks = vec![];
+    for section in Section::ALL {
+        let section_weight = match score.section_weight(section) {
+            Some(weight) if score.global_weight > 0 => weight,
+            _ => continue,
+        };
+        let scored: Vec<_> = registry
+            .section(section)
+            .filter_map(|check| {
+                let passed = should_score(&report.get(check.id()))?;
+                Some((check, passed))
+            })
+            .collect();
+        let checks_weight: usize = scored
+            .iter()
+            .map(|(check, _)| profile.check_weight(*check))
+            .sum();
+        for (check, passed) in scored {
+            let weight = profile.check_weight(check);
+            let points = weight as f64 / checks_weight as f64
+                * 100.0
+                * (section_weight as f64 / score.global_weight as f64);
+            checks.push(CheckContribution {
+                check_id: check.id().to_string(),
+                section,
+                weight,
+                passed,
+                points: if passed { points } else { 0.0 },
+                points_lost: if passed { 0.0 } else { points },
+                repository: None,
+            });
+        }
+    }
+
+    Explanation::new(
+        score.global,
+        score.global_weight,
+        profile.rating_thresholds.clone(),
+        checks,
+    )
+}
+
+/// Merge the explanations provided (one per repository, identified by name)
+/// into a single one, consistent with the score produced by `score::merge`.
+/// The rating thresholds of the first explanation are used.
+pub fn merge_explanations(explanations: Vec<(String, Explanation)>) -> Explanation {
+    let global_weight: usize = explanations.iter().map(|(_, e)| e.global_weight).sum();
+    let rating_thresholds = explanations
+        .first()
+        .map(|(_, e)| e.rating_thresholds.clone())
+        .unwrap_or_default();
+
+    let mut global = 0.0;
+    let mut checks = vec![];
+    if global_weight > 0 {
+        for (repository, e) in explanations {
+            let k = e.global_weight as f64 / global_weight as f64;
+            global += e.global * k;
+            checks.extend(e.checks.into_iter().map(|c| CheckContribution {
+                points: c.points * k,
+                points_lost: c.points_lost * k,
+                repository: Some(repository.clone()),
+                ..c
+            }));
+        }
+    }
+
+    Explanation::new(global, global_weight, rating_thresholds, checks)
+}
+
+impl Explanation {
+    /// Create a new explanation, rating the global score and working out the
+    /// fixes needed to reach the next rating.
+    fn new(
+        global: f64,
+        global_weight: usize,
+        rating_thresholds: RatingThresholds,
+        checks: Vec<CheckContribution>,
+    ) -> Self {
+        let rating = rating_thresholds.rating(global);
+        let next_rating = next_rating(global, rating, &rating_thresholds, &checks);
+        Self {
+            global,
+            global_weight,
+            rating,
+            rating_thresholds,
+            checks,
+            next_rating,
+        }
+    }
+
+    /// Return the checks that failed, sorted by the points lost (desc).
+    pub fn failed(&self) -> Vec<&CheckContribution> {
+        failed(&self.checks)
+    }
+}
+
+/// Return the checks provided that failed, sorted by the points lost (desc).
+fn failed(checks: &[CheckContribution]) -> Vec<&CheckContribution> {
+    let mut failed: Vec<&CheckContribution> = checks.iter().filter(|c| !c.passed).collect();
+    failed.sort_by(|c1, c2| {
+        c2.points_lost
+            .partial_cmp(&c1.points_lost)
+            .unwrap_or(Ordering::Equal)
+    });
+    failed
+}
+
+/// Find the smallest set of failed checks whose fix would reach the rating
+/// above the one provided. As fixing a check does not change the weights, the
+/// points lost by each check can be added up, so picking the checks that lost
+/// more points first gives the smallest set.
+fn next_rating(
+    global: f64,
+    rating: char,
+    thresholds: &RatingThresholds,
+    checks: &[CheckContribution],
+) -> Option<NextRating> {
+    let (next_rating, min_score) = thresholds.next(rating)?;
+    let points_needed = min_score as f64 - global;
+
+    let mut fixes = vec![];
+    let mut points = 0.0;
+    for check in failed(checks) {
+        if points + EPSILON >= points_needed {
+            break;
+        }
+        points += check.points_lost;
+        fixes.push(check.clone());
+    }
+    if points + EPSILON < points_needed {
+        return None;
+    }
+
+    Some(NextRating {
+        rating: next_rating,
+        min_score,
+        points_needed,
+        fixes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::*, linter::*};
+
+    fn report() -> Report {
+        Report {
+            documentation: Documentation {
+                contributing: Some(false.into()),
+                readme: Some(false.into()),
+                ..Default::default()
+            },
+            security: Security {
+                sbom: Some(true.into()),
+                security_policy: Some(false.into()),
+                ..Default::default()
+            },
+            legal: Legal {
+                trademark_disclaimer: Some(true.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn explain_report() {
+        let e = explain(&report(), &Registry::default(), &Profile::default());
+        assert_eq!(e.global_weight, 34);
+        assert_eq!(e.rating, 'd');
+        assert_eq!(e.checks.len(), 5);
+
+        // Points add up to the global score
+        let points: f64 = e.checks.iter().map(|c| c.points).sum();
+        let points_lost: f64 = e.checks.iter().map(|c| c.points_lost).sum();
+        assert!((points - e.global).abs() < EPSILON);
+        assert!((points + points_lost - 100.0).abs() < EPSILON);
+
+        // Fixing the security policy alone is enough to get a c
+        let next = e.next_rating.unwrap();
+        assert_eq!(next.rating, 'c');
+        assert_eq!(next.min_score, 25);
+        assert_eq!(
+            next.fixes
+                .iter()
+                .map(|c| c.check_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![SECURITY_POLICY]
+        );
+    }
+
+    #[test]
+    fn explain_report_needing_several_fixes() {
+        let profile = Profile {
+            rating_thresholds: RatingThresholds {
+                a: 99,
+                b: 95,
+                c: 90,
+            },
+            ..Default::default()
+        };
+        let e = explain(&report(), &Registry::default(), &profile);
+        let next = e.next_rating.unwrap();
+        assert_eq!(next.rating, 'c');
+        assert_eq!(
+            next.fixes
+                .iter()
+                .map(|c| c.check_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![SECURITY_POLICY, README, CONTRIBUTING]
+        );
+    }
+
+    #[test]
+    fn explain_report_with_top_rating_has_no_next_rating() {
+        let report = Report {
+            security: Security {
+                security_policy: Some(true.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let e = explain(&report, &Registry::default(), &Profile::default());
+        assert_eq!(e.rating, 'a');
+        assert_eq!(e.next_rating, None);
+    }
+
+    #[test]
+    fn merge_explanations_works() {
+        let registry = Registry::default();
+        let profile = Profile::default();
+        let e1 = explain(&report(), &registry, &profile);
+        let e2 = explain(&Report::default(), &registry, &profile);
+        let mut r3 = report();
+        r3.documentation.readme = Some(true.into());
+        let e3 = explain(&r3, &registry, &profile);
+        let s1 = calculate(&report(), &registry, &profile);
+        let s3 = calculate(&r3, &registry, &profile);
+
+        let m = merge_explanations(vec![
+            ("repo1".to_string(), e1),
+            ("repo2".to_string(), e2),
+            ("repo3".to_string(), e3),
+        ]);
+        assert!((m.global - super::super::merge(vec![s1, s3]).global).abs() < EPSILON);
+        assert_eq!(m.checks.len(), 10);
+        let next = m.next_rating.unwrap();
+        assert_eq!(next.fixes[0].check_id, SECURITY_POLICY);
+    }
+}
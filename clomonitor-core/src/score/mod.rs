@@ -1,8 +1,14 @@
-use crate::{config::*, linter::CheckResult, linter::Report};
+use crate::linter::{CheckOutput, Registry, Report, Section};
 use serde::{Deserialize, Serialize};
 
+mod explain;
+pub use explain::{explain, merge_explanations, CheckContribution, Explanation, NextRating};
+
+mod profile;
+pub use profile::{Profile, RatingThresholds, DEFAULT_PROFILE};
+
 /// Score information.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Score {
     pub global: f64,
@@ -37,6 +43,12 @@ pub struct Score {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub legal_weight: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating_thresholds: Option<RatingThresholds>,
 }
 
 impl Score {
@@ -56,6 +68,8 @@ impl Score {
             security_weight: None,
             legal: None,
             legal_weight: None,
+            profile: None,
+            rating_thresholds: None,
         }
     }
 
@@ -66,58 +80,83 @@ impl Score {
 
     /// Return the score's rating (a, b, c or d).
     pub fn rating(&self) -> char {
-        rating(self.global())
+        self.rating_for(self.global())
+    }
+
+    /// Return the rating (a, b, c or d) corresponding to the value provided,
+    /// using the thresholds of the profile this score was calculated with
+    /// (the default ones are used if they are unknown).
+    pub fn rating_for(&self, value: f64) -> char {
+        match &self.rating_thresholds {
+            Some(thresholds) => thresholds.rating(value),
+            None => RatingThresholds::default().rating(value),
+        }
+    }
+
+    /// Return the score of the section provided, if it was scored.
+    pub fn section_score(&self, section: Section) -> Option<f64> {
+        match section {
+            Section::Documentation => self.documentation,
+            Section::License => self.license,
+            Section::BestPractices => self.best_practices,
+            Section::Security => self.security,
+            Section::Legal => self.legal,
+        }
+    }
+
+    /// Return the weight of the section provided, if it was scored.
+    pub fn section_weight(&self, section: Section) -> Option<usize> {
+        match section {
+            Section::Documentation => self.documentation_weight,
+            Section::License => self.license_weight,
+            Section::BestPractices => self.best_practices_weight,
+            Section::Security => self.security_weight,
+            Section::Legal => self.legal_weight,
+        }
     }
 }
 
-/// Calculate score for the given linter report.
-pub fn calculate(report: &Report) -> Score {
+/// Calculate score for the given linter report, using the weights of the
+/// checks in the registry provided as adjusted by the scoring profile given.
+pub fn calculate(report: &Report, registry: &Registry, profile: &Profile) -> Score {
     let mut score = Score::new();
-
-    // Documentation
-    let d = &report.documentation;
-    (score.documentation, score.documentation_weight) = calculate_section_score_and_weight(&[
-        (ADOPTERS, should_score(&d.adopters)),
-        (CHANGELOG, should_score(&d.changelog)),
-        (CODE_OF_CONDUCT, should_score(&d.code_of_conduct)),
-        (CONTRIBUTING, should_score(&d.contributing)),
-        (GOVERNANCE, should_score(&d.governance)),
-        (MAINTAINERS, should_score(&d.maintainers)),
-        (README, should_score(&d.readme)),
-        (ROADMAP, should_score(&d.roadmap)),
-        (WEBSITE, should_score(&d.website)),
-    ]);
-
-    // License
-    (score.license, score.license_weight) = calculate_section_score_and_weight(&[
-        (LICENSE_APPROVED, should_score(&report.license.approved)),
-        (LICENSE_SCANNING, should_score(&report.license.scanning)),
-        (LICENSE_SPDX, should_score(&report.license.spdx_id)),
-    ]);
-
-    // Best practices
-    let bp = &report.best_practices;
-    (score.best_practices, score.best_practices_weight) = calculate_section_score_and_weight(&[
-        (ARTIFACTHUB_BADGE, should_score(&bp.artifacthub_badge)),
-        (COMMUNITY_MEETING, should_score(&bp.community_meeting)),
-        (DCO, should_score(&bp.dco)),
-        (OPENSSF_BADGE, should_score(&bp.openssf_badge)),
-        (RECENT_RELEASE, should_score(&bp.recent_release)),
-        (SLACK_PRESENCE, should_score(&bp.slack_presence)),
-    ]);
-
-    // Security
-    let s = &report.security;
-    (score.security, score.security_weight) = calculate_section_score_and_weight(&[
-        (SBOM, should_score(&s.sbom)),
-        (SECURITY_POLICY, should_score(&s.security_policy)),
-    ]);
-
-    // Legal
-    (score.legal, score.legal_weight) = calculate_section_score_and_weight(&[(
-        TRADEMARK_DISCLAIMER,
-        should_score(&report.legal.trademark_disclaimer),
-    )]);
+    score.profile = Some(profile.name.clone());
+    score.rating_thresholds = Some(profile.rating_thresholds.clone());
+
+    // Sections
+    for section in Section::ALL {
+        let checks: Vec<(usize, Option<bool>)> = registry
+            .section(section)
+            .map(|check| {
+                (
+                    profile.check_weight(check),
+                    should_score(&report.get(check.id())),
+                )
+            })
+            .collect();
+        let (section_score, mut section_weight) = calculate_section_score_and_weight(&checks);
+        if section_weight.is_some() {
+            if let Some(weight) = profile.section_weight(section) {
+                section_weight = Some(weight);
+            }
+        }
+        match section {
+            Section::Documentation => {
+                (score.documentation, score.documentation_weight) = (section_score, section_weight)
+            }
+            Section::License => {
+                (score.license, score.license_weight) = (section_score, section_weight)
+            }
+            Section::BestPractices => {
+                (score.best_practices, score.best_practices_weight) =
+                    (section_score, section_weight)
+            }
+            Section::Security => {
+                (score.security, score.security_weight) = (section_score, section_weight)
+            }
+            Section::Legal => (score.legal, score.legal_weight) = (section_score, section_weight),
+        }
+    }
 
     // Global
     let sections_scores = &[
@@ -137,13 +176,16 @@ pub fn calculate(report: &Report) -> Score {
     score.global_weight = sections_weights
         .iter()
         .fold(0, |gw, sw| gw + sw.unwrap_or_default());
-    score.global = sections_scores
-        .iter()
-        .zip(sections_weights.iter())
-        .fold(0.0, |gs, (ss, sw)| {
-            let k = sw.unwrap_or_default() as f64 / score.global_weight as f64;
-            gs + ss.unwrap_or_default() * k
-        });
+    if score.global_weight > 0 {
+        score.global =
+            sections_scores
+                .iter()
+                .zip(sections_weights.iter())
+                .fold(0.0, |gs, (ss, sw)| {
+                    let k = sw.unwrap_or_default() as f64 / score.global_weight as f64;
+                    gs + ss.unwrap_or_default() * k
+                });
+    }
 
     score
 }
@@ -179,8 +221,22 @@ pub fn merge(scores: Vec<Score>) -> Score {
         merged
     };
 
-    // Calculate merged score for each of the sections.
+    // The merged score keeps the profile (and its rating thresholds) only if
+    // all scores share it
     let mut m = Score::new();
+    if let Some(first) = scores.first() {
+        if scores.iter().all(|s| s.profile == first.profile) {
+            m.profile = first.profile.clone();
+        }
+        if scores
+            .iter()
+            .all(|s| s.rating_thresholds == first.rating_thresholds)
+        {
+            m.rating_thresholds = first.rating_thresholds.clone();
+        }
+    }
+
+    // Calculate merged score for each of the sections.
     for s in scores {
         m.global += s.global * (s.global_weight as f64 / global_weights_sum as f64);
         m.documentation = merge(
@@ -213,26 +269,26 @@ pub fn merge(scores: Vec<Score>) -> Score {
     m
 }
 
-/// Return the score's rating (a, b, c or d).
-pub fn rating(score: f64) -> char {
-    match score as usize {
-        75..=100 => 'a',
-        50..=74 => 'b',
-        25..=49 => 'c',
-        0..=24 => 'd',
-        _ => '?',
+/// Return the width of the bar used to display the section score provided,
+/// on a scale of 106 units. A minimum width is used so that low scores are
+/// still visible.
+pub fn section_bar_width(score: Option<f64>) -> f64 {
+    match score {
+        Some(v) => (v * 1.06).round().max(2.0),
+        None => 0.0,
     }
 }
 
-/// Calculate score for a report's section from the checks provided.
+/// Calculate score for a report's section from the checks provided (weight
+/// and whether the check should score or not).
 fn calculate_section_score_and_weight(
-    checks: &[(&'static str, Option<bool>)],
+    checks: &[(usize, Option<bool>)],
 ) -> (Option<f64>, Option<usize>) {
     // Calculate section weight
     let mut section_weight = 0;
-    for (check_id, should_score) in checks {
+    for (weight, should_score) in checks {
         if should_score.is_some() {
-            section_weight += CHECK_WEIGHT[check_id];
+            section_weight += weight;
         }
     }
 
@@ -243,10 +299,10 @@ fn calculate_section_score_and_weight(
 
     // Calculate section score
     let mut score = 0.0;
-    for (check_id, should_score) in checks {
+    for (weight, should_score) in checks {
         if let Some(should_score) = should_score {
             if *should_score {
-                score += CHECK_WEIGHT[check_id] as f64 / section_weight as f64 * 100.0;
+                score += *weight as f64 / section_weight as f64 * 100.0;
             }
         }
     }
@@ -255,15 +311,19 @@ fn calculate_section_score_and_weight(
 }
 
 /// Helper that checks if the provided check should be scored or not. At the
-/// moment a check gets a score if it has passed or is exempt.
-fn should_score<T>(r: &Option<CheckResult<T>>) -> Option<bool> {
-    r.as_ref().map(|r| r.passed || r.exempt)
+/// moment a check gets a score if it has passed or is exempt. Checks that were
+/// not evaluated or that failed with an error are not taken into account, like
+/// the ones not provided.
+fn should_score(r: &Option<CheckOutput>) -> Option<bool> {
+    r.as_ref()
+        .filter(|r| !r.not_evaluated && r.error.is_none())
+        .map(|r| r.passed || r.exempt)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::linter::*;
+    use crate::{config::*, linter::*};
 
     #[test]
     fn new_returns_empty_score() {
@@ -282,6 +342,8 @@ mod tests {
                 security_weight: None,
                 legal: None,
                 legal_weight: None,
+                profile: None,
+                rating_thresholds: None,
             }
         );
     }
@@ -311,61 +373,89 @@ mod tests {
     }
 
     #[test]
-    fn rating_returns_correct_level() {
-        assert_eq!(rating(80.0), 'a');
-        assert_eq!(rating(75.0), 'a');
-        assert_eq!(rating(74.0), 'b');
-        assert_eq!(rating(50.0), 'b');
-        assert_eq!(rating(49.0), 'c');
-        assert_eq!(rating(25.0), 'c');
-        assert_eq!(rating(20.0), 'd');
+    fn score_rating_for_returns_correct_level() {
+        let score = Score::new();
+        assert_eq!(score.rating_for(80.0), 'a');
+        assert_eq!(score.rating_for(75.0), 'a');
+        assert_eq!(score.rating_for(74.0), 'b');
+        assert_eq!(score.rating_for(50.0), 'b');
+        assert_eq!(score.rating_for(49.0), 'c');
+        assert_eq!(score.rating_for(25.0), 'c');
+        assert_eq!(score.rating_for(20.0), 'd');
+    }
+
+    #[test]
+    fn score_rating_uses_profile_thresholds() {
+        let profile = Profile {
+            rating_thresholds: RatingThresholds {
+                a: 90,
+                b: 80,
+                c: 70,
+            },
+            ..Profile::default()
+        };
+        let score = calculate(&Report::default(), &Registry::default(), &profile);
+        assert_eq!(score.rating_for(85.0), 'b');
+        assert_eq!(score.rating_for(75.0), 'c');
+
+        let score: Score = serde_yaml::from_str(&serde_yaml::to_string(&score).unwrap()).unwrap();
+        assert_eq!(score.rating_for(85.0), 'b');
     }
 
     #[test]
     fn calculate_report_with_all_checks_passed_got_max_score() {
         assert_eq!(
-            calculate(&Report {
-                documentation: Documentation {
-                    adopters: Some(true.into()),
-                    code_of_conduct: Some(true.into()),
-                    contributing: Some(true.into()),
-                    changelog: Some(true.into()),
-                    governance: Some(true.into()),
-                    maintainers: Some(true.into()),
-                    readme: Some(true.into()),
-                    roadmap: Some(true.into()),
-                    website: Some(true.into()),
-                },
-                license: License {
-                    approved: Some(CheckResult {
-                        passed: true,
-                        value: Some(true),
+            calculate(
+                &Report {
+                    documentation: Documentation {
+                        adopters: Some(true.into()),
+                        code_of_conduct: Some(true.into()),
+                        contributing: Some(true.into()),
+                        changelog: Some(true.into()),
+                        governance: Some(true.into()),
+                        maintainers: Some(true.into()),
+                        readme: Some(true.into()),
+                        roadmap: Some(true.into()),
+                        website: Some(true.into()),
                         ..Default::default()
-                    }),
-                    scanning: Some(CheckResult::from_url(Some(
-                        "https://license-scanning.url".to_string()
-                    ))),
-                    spdx_id: Some(Some("Apache-2.0".to_string()).into()),
-                },
-                best_practices: BestPractices {
-                    artifacthub_badge: Some(CheckResult {
-                        exempt: true,
+                    },
+                    license: License {
+                        approved: Some(CheckResult {
+                            passed: true,
+                            value: Some(true),
+                            ..Default::default()
+                        }),
+                        scanning: Some(CheckResult::from_url(Some(
+                            "https://license-scanning.url".to_string()
+                        ))),
+                        spdx_id: Some(Some("Apache-2.0".to_string()).into()),
                         ..Default::default()
-                    }),
-                    community_meeting: Some(true.into()),
-                    dco: Some(true.into()),
-                    openssf_badge: Some(true.into()),
-                    recent_release: Some(true.into()),
-                    slack_presence: Some(true.into()),
-                },
-                security: Security {
-                    sbom: Some(true.into()),
-                    security_policy: Some(true.into()),
-                },
-                legal: Legal {
-                    trademark_disclaimer: Some(true.into()),
+                    },
+                    best_practices: BestPractices {
+                        artifacthub_badge: Some(CheckResult {
+                            exempt: true,
+                            ..Default::default()
+                        }),
+                        community_meeting: Some(true.into()),
+                        dco: Some(true.into()),
+                        openssf_badge: Some(true.into()),
+                        recent_release: Some(true.into()),
+                        slack_presence: Some(true.into()),
+                        ..Default::default()
+                    },
+                    security: Security {
+                        sbom: Some(true.into()),
+                        security_policy: Some(true.into()),
+                        ..Default::default()
+                    },
+                    legal: Legal {
+                        trademark_disclaimer: Some(true.into()),
+                        ..Default::default()
+                    },
                 },
-            }),
+                &Registry::default(),
+                &Profile::default()
+            ),
             Score {
                 global: 99.99999999999999,
                 global_weight: 90,
@@ -379,6 +469,8 @@ mod tests {
                 security_weight: Some(15),
                 legal: Some(100.0),
                 legal_weight: Some(5),
+                profile: Some(DEFAULT_PROFILE.to_string()),
+                rating_thresholds: Some(RatingThresholds::default()),
             }
         );
     }
@@ -386,42 +478,51 @@ mod tests {
     #[test]
     fn calculate_report_with_no_checks_passed_got_min_score() {
         assert_eq!(
-            calculate(&Report {
-                documentation: Documentation {
-                    adopters: Some(false.into()),
-                    code_of_conduct: Some(false.into()),
-                    contributing: Some(false.into()),
-                    changelog: Some(false.into()),
-                    governance: Some(false.into()),
-                    maintainers: Some(false.into()),
-                    readme: Some(false.into()),
-                    roadmap: Some(false.into()),
-                    website: Some(false.into()),
-                },
-                license: License {
-                    approved: Some(false.into()),
-                    scanning: Some(false.into()),
-                    spdx_id: Some(false.into()),
-                },
-                best_practices: BestPractices {
-                    artifacthub_badge: Some(CheckResult {
-                        exempt: false,
+            calculate(
+                &Report {
+                    documentation: Documentation {
+                        adopters: Some(false.into()),
+                        code_of_conduct: Some(false.into()),
+                        contributing: Some(false.into()),
+                        changelog: Some(false.into()),
+                        governance: Some(false.into()),
+                        maintainers: Some(false.into()),
+                        readme: Some(false.into()),
+                        roadmap: Some(false.into()),
+                        website: Some(false.into()),
                         ..Default::default()
-                    }),
-                    community_meeting: Some(false.into()),
-                    dco: Some(false.into()),
-                    openssf_badge: Some(false.into()),
-                    recent_release: Some(false.into()),
-                    slack_presence: Some(false.into()),
-                },
-                security: Security {
-                    sbom: Some(false.into()),
-                    security_policy: Some(false.into()),
-                },
-                legal: Legal {
-                    trademark_disclaimer: Some(false.into()),
+                    },
+                    license: License {
+                        approved: Some(false.into()),
+                        scanning: Some(false.into()),
+                        spdx_id: Some(false.into()),
+                        ..Default::default()
+                    },
+                    best_practices: BestPractices {
+                        artifacthub_badge: Some(CheckResult {
+                            exempt: false,
+                            ..Default::default()
+                        }),
+                        community_meeting: Some(false.into()),
+                        dco: Some(false.into()),
+                        openssf_badge: Some(false.into()),
+                        recent_release: Some(false.into()),
+                        slack_presence: Some(false.into()),
+                        ..Default::default()
+                    },
+                    security: Security {
+                        sbom: Some(false.into()),
+                        security_policy: Some(false.into()),
+                        ..Default::default()
+                    },
+                    legal: Legal {
+                        trademark_disclaimer: Some(false.into()),
+                        ..Default::default()
+                    },
                 },
-            }),
+                &Registry::default(),
+                &Profile::default()
+            ),
             Score {
                 global: 0.0,
                 global_weight: 90,
@@ -435,6 +536,8 @@ mod tests {
                 security_weight: Some(15),
                 legal: Some(0.0),
                 legal_weight: Some(5),
+                profile: Some(DEFAULT_PROFILE.to_string()),
+                rating_thresholds: Some(RatingThresholds::default()),
             }
         );
     }
@@ -442,48 +545,57 @@ mod tests {
     #[test]
     fn calculate_report_with_all_checks_passed_but_some_missing_got_max_score() {
         assert_eq!(
-            calculate(&Report {
-                documentation: Documentation {
-                    adopters: None,
-                    code_of_conduct: None,
-                    contributing: Some(true.into()),
-                    changelog: Some(true.into()),
-                    governance: None,
-                    maintainers: Some(true.into()),
-                    readme: Some(true.into()),
-                    roadmap: None,
-                    website: None,
-                },
-                license: License {
-                    approved: Some(CheckResult {
-                        passed: true,
-                        value: Some(true),
+            calculate(
+                &Report {
+                    documentation: Documentation {
+                        adopters: None,
+                        code_of_conduct: None,
+                        contributing: Some(true.into()),
+                        changelog: Some(true.into()),
+                        governance: None,
+                        maintainers: Some(true.into()),
+                        readme: Some(true.into()),
+                        roadmap: None,
+                        website: None,
                         ..Default::default()
-                    }),
-                    scanning: Some(CheckResult::from_url(Some(
-                        "https://license-scanning.url".to_string()
-                    ))),
-                    spdx_id: Some(Some("Apache-2.0".to_string()).into()),
-                },
-                best_practices: BestPractices {
-                    artifacthub_badge: Some(CheckResult {
-                        exempt: true,
+                    },
+                    license: License {
+                        approved: Some(CheckResult {
+                            passed: true,
+                            value: Some(true),
+                            ..Default::default()
+                        }),
+                        scanning: Some(CheckResult::from_url(Some(
+                            "https://license-scanning.url".to_string()
+                        ))),
+                        spdx_id: Some(Some("Apache-2.0".to_string()).into()),
                         ..Default::default()
-                    }),
-                    community_meeting: None,
-                    dco: Some(true.into()),
-                    openssf_badge: Some(true.into()),
-                    recent_release: Some(true.into()),
-                    slack_presence: None,
-                },
-                security: Security {
-                    sbom: Some(true.into()),
-                    security_policy: Some(true.into()),
-                },
-                legal: Legal {
-                    trademark_disclaimer: None,
+                    },
+                    best_practices: BestPractices {
+                        artifacthub_badge: Some(CheckResult {
+                            exempt: true,
+                            ..Default::default()
+                        }),
+                        community_meeting: None,
+                        dco: Some(true.into()),
+                        openssf_badge: Some(true.into()),
+                        recent_release: Some(true.into()),
+                        slack_presence: None,
+                        ..Default::default()
+                    },
+                    security: Security {
+                        sbom: Some(true.into()),
+                        security_policy: Some(true.into()),
+                        ..Default::default()
+                    },
+                    legal: Legal {
+                        trademark_disclaimer: None,
+                        ..Default::default()
+                    },
                 },
-            }),
+                &Registry::default(),
+                &Profile::default()
+            ),
             Score {
                 global: 100.0,
                 global_weight: 69,
@@ -497,10 +609,79 @@ mod tests {
                 security_weight: Some(15),
                 legal: None,
                 legal_weight: None,
+                profile: Some(DEFAULT_PROFILE.to_string()),
+                rating_thresholds: Some(RatingThresholds::default()),
             }
         );
     }
 
+    #[test]
+    fn calculate_report_with_checks_not_evaluated_ignores_them() {
+        let report = Report {
+            best_practices: BestPractices {
+                dco: Some(true.into()),
+                recent_release: Some(CheckResult::new_not_evaluated()),
+                ..Default::default()
+            },
+            legal: Legal {
+                trademark_disclaimer: Some(CheckResult::new_not_evaluated()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let score = calculate(&report, &Registry::default(), &Profile::default());
+        assert_eq!(score.best_practices, Some(100.0));
+        assert_eq!(score.best_practices_weight, Some(CHECK_WEIGHT[DCO]));
+        assert_eq!(score.legal, None);
+    }
+
+    #[test]
+    fn calculate_report_with_checks_errored_ignores_them() {
+        let report = Report {
+            security: Security {
+                sbom: Some(CheckResult::from(anyhow::format_err!("unavailable"))),
+                security_policy: Some(true.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let score = calculate(&report, &Registry::default(), &Profile::default());
+        assert_eq!(score.security, Some(100.0));
+        assert_eq!(score.security_weight, Some(CHECK_WEIGHT[SECURITY_POLICY]));
+        assert_eq!(
+            report.errors(&Registry::default()),
+            vec![(SBOM.to_string(), "unavailable".to_string())]
+        );
+    }
+
+    #[test]
+    fn calculate_report_using_profile() {
+        let report = Report {
+            security: Security {
+                sbom: Some(true.into()),
+                security_policy: Some(false.into()),
+                ..Default::default()
+            },
+            legal: Legal {
+                trademark_disclaimer: Some(true.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let profile = Profile {
+            name: "security-first".to_string(),
+            check_weights: [(SBOM.to_string(), 13)].into_iter().collect(),
+            section_weights: [(Section::Security, 15)].into_iter().collect(),
+            ..Default::default()
+        };
+        let score = calculate(&report, &Registry::default(), &profile);
+        assert_eq!(score.security, Some(50.0));
+        assert_eq!(score.security_weight, Some(15));
+        assert_eq!(score.legal, Some(100.0));
+        assert_eq!(score.global, 62.5);
+        assert_eq!(score.profile, Some("security-first".to_string()));
+    }
+
     #[test]
     fn merge_scores() {
         assert_eq!(
@@ -518,6 +699,8 @@ mod tests {
                     security_weight: Some(15),
                     legal: Some(100.0),
                     legal_weight: Some(5),
+                    profile: None,
+                    rating_thresholds: None,
                 },
                 Score {
                     global: 0.0,
@@ -532,6 +715,8 @@ mod tests {
                     security_weight: Some(10),
                     legal: None,
                     legal_weight: None,
+                    profile: None,
+                    rating_thresholds: None,
                 }
             ]),
             Score {
@@ -547,7 +732,17 @@ mod tests {
                 security_weight: None,
                 legal: Some(100.0),
                 legal_weight: None,
+                profile: None,
+                rating_thresholds: None,
             }
         )
     }
+
+    #[test]
+    fn section_bar_width_works() {
+        assert_eq!(section_bar_width(Some(100.0)), 106.0);
+        assert_eq!(section_bar_width(Some(50.0)), 53.0);
+        assert_eq!(section_bar_width(Some(0.0)), 2.0);
+        assert_eq!(section_bar_width(None), 0.0);
+    }
 }
@@ -0,0 +1,187 @@
+use crate::linter::{Check, Section};
+use anyhow::{format_err, Error};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+/// Name of the profile used when none is provided.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Scoring profile, used to customize how the score is calculated.
+///
+/// Profiles can override the weight of any of the checks (the weight defined
+/// by the check is used otherwise), the weight of the sections when
+/// calculating the global score (by default a section weighs as much as the
+/// checks scored in it) and the thresholds used to calculate the rating.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub name: String,
+
+    #[serde(default)]
+    pub check_weights: HashMap<String, usize>,
+
+    #[serde(default)]
+    pub section_weights: HashMap<Section, usize>,
+
+    #[serde(default)]
+    pub rating_thresholds: RatingThresholds,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PROFILE.to_string(),
+            check_weights: HashMap::new(),
+            section_weights: HashMap::new(),
+            rating_thresholds: RatingThresholds::default(),
+        }
+    }
+}
+
+impl Profile {
+    /// Load the profile from the YAML file located at the path provided.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let profile: Profile = serde_yaml::from_str(&fs::read_to_string(path)?)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Load the list of profiles from the YAML file located at the path
+    /// provided. Profiles must be declared in the `profiles` entry.
+    pub fn from_file_list<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, Error> {
+        #[derive(Deserialize)]
+        struct ProfilesFile {
+            profiles: Vec<Profile>,
+        }
+
+        let file: ProfilesFile = serde_yaml::from_str(&fs::read_to_string(path)?)?;
+        for profile in &file.profiles {
+            profile.validate()?;
+        }
+        Ok(file.profiles)
+    }
+
+    /// Check the profile is valid.
+    fn validate(&self) -> Result<(), Error> {
+        if self.name.is_empty() {
+            return Err(format_err!("profile name cannot be empty"));
+        }
+        let t = &self.rating_thresholds;
+        if !(t.a <= 100 && t.a > t.b && t.b > t.c) {
+            return Err(format_err!(
+                "invalid rating thresholds in profile {} (expected 100 >= a > b > c)",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return the weight of the check provided in this profile.
+    pub fn check_weight(&self, check: &dyn Check) -> usize {
+        *self
+            .check_weights
+            .get(check.id())
+            .unwrap_or(&check.weight())
+    }
+
+    /// Return the weight of the section provided in this profile, if it has
+    /// been overridden.
+    pub fn section_weight(&self, section: Section) -> Option<usize> {
+        self.section_weights.get(&section).copied()
+    }
+
+    /// Return the rating (a, b, c or d) corresponding to the score provided.
+    pub fn rating(&self, score: f64) -> char {
+        self.rating_thresholds.rating(score)
+    }
+}
+
+/// Minimum scores required to get each of the ratings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingThresholds {
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl Default for RatingThresholds {
+    fn default() -> Self {
+        Self {
+            a: 75,
+            b: 50,
+            c: 25,
+        }
+    }
+}
+
+impl RatingThresholds {
+    /// Return the rating (a, b, c or d) corresponding to the score provided.
+    pub fn rating(&self, score: f64) -> char {
+        match score as usize {
+            v if v > 100 => '?',
+            v if v >= self.a => 'a',
+            v if v >= self.b => 'b',
+            v if v >= self.c => 'c',
+            _ => 'd',
+        }
+    }
+
+    /// Return the rating above the one provided along with the minimum score
+    /// required to get it, if any.
+    pub fn next(&self, rating: char) -> Option<(char, usize)> {
+        match rating {
+            'b' => Some(('a', self.a)),
+            'c' => Some(('b', self.b)),
+            'd' => Some(('c', self.c)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::*, linter::Registry};
+
+    const TESTDATA_PATH: &str = "src/score/testdata";
+
+    #[test]
+    fn profile_from_file() {
+        let profile = Profile::from_file(Path::new(TESTDATA_PATH).join("profile.yml")).unwrap();
+        let registry = Registry::default();
+        assert_eq!(profile.name, "security-first");
+        assert_eq!(profile.check_weight(registry.get(SBOM).unwrap()), 10);
+        assert_eq!(profile.check_weight(registry.get(README).unwrap()), 10);
+        assert_eq!(profile.section_weight(Section::Security), Some(40));
+        assert_eq!(profile.section_weight(Section::Legal), None);
+        assert_eq!(profile.rating(79.0), 'b');
+    }
+
+    #[test]
+    fn profiles_from_file_list() {
+        let profiles =
+            Profile::from_file_list(Path::new(TESTDATA_PATH).join("profiles.yml")).unwrap();
+        assert_eq!(
+            profiles.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+            vec!["security-first", "docs-only"]
+        );
+    }
+
+    #[test]
+    fn profile_with_invalid_thresholds_fails() {
+        assert!(Profile::from_file(Path::new(TESTDATA_PATH).join("invalid-profile.yml")).is_err());
+    }
+
+    #[test]
+    fn default_rating_thresholds() {
+        let t = RatingThresholds::default();
+        assert_eq!(t.rating(100.0), 'a');
+        assert_eq!(t.rating(75.0), 'a');
+        assert_eq!(t.rating(74.9), 'b');
+        assert_eq!(t.rating(25.0), 'c');
+        assert_eq!(t.rating(0.0), 'd');
+        assert_eq!(t.rating(101.0), '?');
+        assert_eq!(t.next('b'), Some(('a', 75)));
+        assert_eq!(t.next('a'), None);
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{format_err, Error};
+use clomonitor_core::{
+    linter::{CheckOutput, Registry, Report},
+    score::{self, Profile},
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{fs, path::Path};
+
+/// Changes in the linter results since a baseline report.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub(crate) struct Changes {
+    pub newly_failing: Vec<String>,
+    pub newly_passing: Vec<String>,
+    pub previous_score: f64,
+    pub score_delta: f64,
+}
+
+impl Changes {
+    /// Check if the results have regressed since the baseline: a check that
+    /// used to pass now fails or the global score dropped more points than
+    /// the tolerance provided.
+    pub(crate) fn regressed(&self, tolerance: f64) -> bool {
+        !self.newly_failing.is_empty() || -self.score_delta > tolerance
+    }
+}
+
+/// Load the baseline report from the JSON file located at the path provided.
+/// Both documents produced using `--format json` and bare reports are
+/// supported.
+pub(crate) fn load(path: &Path) -> Result<Report, Error> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format_err!("error reading baseline {}: {err}", path.display()))?;
+    let mut doc: Value = serde_json::from_str(&content)?;
+    let report = match doc.get_mut("report") {
+        Some(report) => report.take(),
+        None => doc,
+    };
+    Ok(serde_json::from_value(report)?)
+}
+
+/// Compare the current report with the baseline one, check by check. Both
+/// reports are scored using the registry and profile provided.
+pub(crate) fn compare(
+    current: &Report,
+    baseline: &Report,
+    registry: &Registry,
+    profile: &Profile,
+) -> Changes {
+    let mut changes = Changes::default();
+    for check in registry.iter() {
+        let before = baseline.get(check.id());
+        let after = current.get(check.id());
+        match (status(&before), status(&after)) {
+            (Some(true), Some(false)) => changes.newly_failing.push(check.id().to_string()),
+            (Some(false), Some(true)) => changes.newly_passing.push(check.id().to_string()),
+            _ => {}
+        }
+    }
+    let previous_score = score::calculate(baseline, registry, profile).global();
+    let current_score = score::calculate(current, registry, profile).global();
+    changes.previous_score = previous_score;
+    changes.score_delta = current_score - previous_score;
+    changes
+}
+
+/// Return whether the check result provided is passing (passed or exempt) or
+/// not. Results not available, not evaluated or that failed with an error
+/// can't be compared.
+fn status(r: &Option<CheckOutput>) -> Option<bool> {
+    r.as_ref()
+        .filter(|r| !r.not_evaluated && r.error.is_none())
+        .map(|r| r.passed || r.exempt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    #[test]
+    fn compare_reports() {
+        let mut baseline = Report::default();
+        baseline.documentation.adopters = Some(true.into());
+        baseline.documentation.changelog = Some(false.into());
+        baseline.documentation.readme = Some(true.into());
+        let mut current = Report::default();
+        current.documentation.adopters = Some(false.into());
+        current.documentation.changelog = Some(true.into());
+        current.documentation.readme = Some(true.into());
+        current.documentation.roadmap = Some(false.into());
+
+        let changes = compare(
+            &current,
+            &baseline,
+            &Registry::default(),
+            &Profile::default(),
+        );
+        assert_eq!(changes.newly_failing, vec!["adopters"]);
+        assert_eq!(changes.newly_passing, vec!["changelog"]);
+        assert!(changes.score_delta < 0.0);
+        assert!(changes.regressed(100.0));
+    }
+
+    #[test]
+    fn regressed_when_score_drops_past_tolerance() {
+        let changes = Changes {
+            score_delta: -3.0,
+            ..Default::default()
+        };
+        assert!(!changes.regressed(5.0));
+        assert!(changes.regressed(2.0));
+        assert!(!Changes::default().regressed(0.0));
+    }
+
+    #[test]
+    fn load_output_document_and_bare_report() {
+        let tmp_dir = TempDir::new("clomonitor").unwrap();
+        let dir = tmp_dir.path();
+        let report = json!({
+            "documentation": {"adopters": {"passed": true, "exempt": false}},
+            "license": {},
+            "best_practices": {},
+            "security": {},
+            "legal": {},
+        });
+
+        let bare = dir.join("bare.json");
+        fs::write(&bare, report.to_string()).unwrap();
+        assert!(load(&bare).unwrap().documentation.adopters.unwrap().passed);
+
+        let doc = dir.join("doc.json");
+        fs::write(
+            &doc,
+            json!({"schema_version": 1, "report": report}).to_string(),
+        )
+        .unwrap();
+        assert!(load(&doc).unwrap().documentation.adopters.unwrap().passed);
+    }
+}
@@ -1,139 +1,498 @@
+use crate::{baseline::Changes, docs::check_set_names, project::RepositoryResults};
 use clomonitor_core::{
-    linter::{CheckResult, Report},
-    score::Score,
+    linter::{
+        CheckDetails, CheckInfo, CheckOutput, CheckResult, CheckValue, Registry, Report, Section,
+        Source,
+    },
+    score::{self, Explanation, Profile, Score},
 };
-use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table, *};
+use comfy_table::{
+    modifiers::UTF8_ROUND_CORNERS,
+    presets::{ASCII_FULL, UTF8_FULL},
+    Table, *,
+};
+use std::{collections::HashMap, fmt::Write};
+
+/// Append a line, formatted like println, to the output string provided.
+macro_rules! out_line {
+    ($out:expr, $($arg:tt)*) => {
+        writeln!($out, $($arg)*).expect("writing to a string never fails")
+    };
+}
 
-pub(crate) const SUCCESS_SYMBOL: char = '✓';
-pub(crate) const FAILURE_SYMBOL: char = '✗';
+pub(crate) const SUCCESS_SYMBOL: &str = "✓";
+pub(crate) const FAILURE_SYMBOL: &str = "✗";
+pub(crate) const ASCII_SUCCESS_SYMBOL: &str = "PASS";
+pub(crate) const ASCII_FAILURE_SYMBOL: &str = "FAIL";
 pub(crate) const NOT_APPLICABLE_MSG: &str = "n/a";
 pub(crate) const EXEMPT_MSG: &str = "Exempt";
+pub(crate) const NOT_EVALUATED_MSG: &str = "Not evaluated";
+pub(crate) const ERROR_MSG: &str = "Error";
+
+/// Options used to display the linter results.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DisplayOptions {
+    /// Display additional details about each check (evidence url, exemption
+    /// reason, weight and points contributed) and the sections weights.
+    pub verbose: bool,
+
+    /// Do not use colors, even when the output is a terminal.
+    pub no_color: bool,
+
+    /// Use plain ASCII characters only (to draw the tables and to mark the
+    /// checks passed and failed).
+    pub ascii: bool,
+}
+
+impl DisplayOptions {
+    /// Symbol used to mark successes (i.e. checks passed).
+    pub(crate) fn success_symbol(&self) -> &'static str {
+        match self.ascii {
+            true => ASCII_SUCCESS_SYMBOL,
+            false => SUCCESS_SYMBOL,
+        }
+    }
+
+    /// Symbol used to mark failures (i.e. checks failed).
+    pub(crate) fn failure_symbol(&self) -> &'static str {
+        match self.ascii {
+            true => ASCII_FAILURE_SYMBOL,
+            false => FAILURE_SYMBOL,
+        }
+    }
+}
 
 /// Print the linter results provided.
-pub(crate) fn display(report: &Report, score: &Score) {
-    println!("CLOMonitor linter results\n");
+pub(crate) fn display(
+    report: &Report,
+    score: &Score,
+    registry: &Registry,
+    profile: &Profile,
+    options: &DisplayOptions,
+) {
+    print!("{}", render(report, score, registry, profile, options));
+}
+
+/// Render the linter results provided, as printed by display.
+fn render(
+    report: &Report,
+    score: &Score,
+    registry: &Registry,
+    profile: &Profile,
+    options: &DisplayOptions,
+) -> String {
+    let mut out = String::new();
+    out_line!(out, "CLOMonitor linter results\n");
 
     // Summary table
-    println!("Score summary\n");
-    let mut summary = Table::new();
-    summary
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![cell_header("Section"), cell_header("Score")])
-        .add_row(vec![cell_entry("Global"), cell_score(Some(score.global))])
-        .add_row(vec![
-            cell_entry("Documentation"),
-            cell_score(score.documentation),
-        ])
-        .add_row(vec![cell_entry("License"), cell_score(score.license)])
-        .add_row(vec![
-            cell_entry("Best practices"),
-            cell_score(score.best_practices),
-        ])
-        .add_row(vec![cell_entry("Security"), cell_score(score.security)])
-        .add_row(vec![cell_entry("Legal"), cell_score(score.legal)]);
-    println!("{summary}\n");
+    out_line!(out, "Score summary\n");
+    let mut summary = new_table(options);
+    let mut header = vec![cell_header("Section"), cell_header("Score")];
+    if options.verbose {
+        header.push(cell_header("Weight"));
+    }
+    summary.set_header(header);
+    let mut global_row = vec![cell_entry("Global"), cell_score(Some(score.global))];
+    if options.verbose {
+        global_row.push(cell_weight(Some(score.global_weight)));
+    }
+    summary.add_row(global_row);
+    for section in Section::ALL {
+        let mut row = vec![
+            cell_entry(&section.to_string()),
+            cell_score(score.section_score(section)),
+        ];
+        if options.verbose {
+            row.push(cell_weight(score.section_weight(section)));
+        }
+        summary.add_row(row);
+    }
+    out_line!(out, "{summary}\n");
 
     // Checks table
-    println!("Checks summary\n");
-    let mut checks = Table::new();
-    checks
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![cell_header("Check"), cell_header("Passed")])
-        .add_row(vec![
-            cell_entry("Documentation / Adopters"),
-            cell_check(&report.documentation.adopters),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Changelog"),
-            cell_check(&report.documentation.changelog),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Code of conduct"),
-            cell_check(&report.documentation.code_of_conduct),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Contributing"),
-            cell_check(&report.documentation.contributing),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Governance"),
-            cell_check(&report.documentation.governance),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Maintainers"),
-            cell_check(&report.documentation.maintainers),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Readme"),
-            cell_check(&report.documentation.readme),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Roadmap"),
-            cell_check(&report.documentation.roadmap),
-        ])
-        .add_row(vec![
-            cell_entry("Documentation / Website"),
-            cell_check(&report.documentation.website),
-        ])
-        .add_row(vec![
-            cell_entry("License"),
-            Cell::new(match &report.license.spdx_id {
-                None => NOT_APPLICABLE_MSG.to_string(),
-                Some(r) => r
-                    .value
-                    .clone()
-                    .unwrap_or_else(|| "Not detected".to_string()),
-            })
-            .set_alignment(CellAlignment::Center)
-            .add_attribute(Attribute::Bold),
-        ])
-        .add_row(vec![
-            cell_entry("License / Approved"),
-            cell_check(&report.license.approved),
-        ])
-        .add_row(vec![
-            cell_entry("License / Scanning"),
-            cell_check(&report.license.scanning),
-        ])
-        .add_row(vec![
-            cell_entry("Best practices / Artifact Hub badge"),
-            cell_check(&report.best_practices.artifacthub_badge),
-        ])
-        .add_row(vec![
-            cell_entry("Best practices / Community meeting"),
-            cell_check(&report.best_practices.community_meeting),
-        ])
-        .add_row(vec![
-            cell_entry("Best practices / DCO"),
-            cell_check(&report.best_practices.dco),
-        ])
-        .add_row(vec![
-            cell_entry("Best practices / OpenSSF (CII) badge"),
-            cell_check(&report.best_practices.openssf_badge),
-        ])
-        .add_row(vec![
-            cell_entry("Best practices / Recent release"),
-            cell_check(&report.best_practices.recent_release),
-        ])
-        .add_row(vec![
-            cell_entry("Best practices / Slack presence"),
-            cell_check(&report.best_practices.slack_presence),
-        ])
-        .add_row(vec![
-            cell_entry("Security / SBOM"),
-            cell_check(&report.security.sbom),
-        ])
-        .add_row(vec![
-            cell_entry("Security / Security policy"),
-            cell_check(&report.security.security_policy),
-        ])
-        .add_row(vec![
-            cell_entry("Legal / Trademark disclaimer"),
-            cell_check(&report.legal.trademark_disclaimer),
+    out_line!(out, "Checks summary\n");
+    let mut checks = new_table(options);
+    if options.verbose {
+        let explanation = score::explain(report, registry, profile);
+        let contributions: HashMap<&str, _> = explanation
+            .checks
+            .iter()
+            .map(|c| (c.check_id.as_str(), c))
+            .collect();
+        checks.set_header(vec![
+            cell_header("Check"),
+            cell_header("Passed"),
+            cell_header("Value"),
+            cell_header("Weight"),
+            cell_header("Points"),
+            cell_header("URL"),
+            cell_header("Exemption reason"),
+        ]);
+        for check in registry.iter() {
+            let r = report.get(check.id());
+            let contribution = contributions.get(check.id());
+            checks.add_row(vec![
+                cell_entry(&format!("{} / {}", check.section(), check.name())),
+                cell_check_status(&r, options),
+                cell_entry(&r.as_ref().and_then(format_value).unwrap_or_default()),
+                cell_weight(contribution.map(|c| c.weight)),
+                match contribution {
+                    Some(c) => cell_points(c.points),
+                    None => cell_entry(""),
+                },
+                cell_entry(&r.as_ref().and_then(evidence_url).unwrap_or_default()),
+                cell_entry(
+                    r.as_ref()
+                        .and_then(|r| r.exemption_reason.as_deref())
+                        .unwrap_or_default(),
+                ),
+            ]);
+        }
+    } else {
+        checks.set_header(vec![cell_header("Check"), cell_header("Passed")]);
+        for check in registry.iter() {
+            checks.add_row(vec![
+                cell_entry(&format!("{} / {}", check.section(), check.name())),
+                cell_check(&report.get(check.id()), options),
+            ]);
+        }
+    }
+    out_line!(out, "{checks}\n");
+
+    // Details about the checks that failed
+    let failures: Vec<String> = registry
+        .iter()
+        .filter_map(|check| {
+            let r = report.get(check.id())?;
+            if r.passed || r.exempt || r.not_evaluated || r.error.is_some() {
+                return None;
+            }
+            let mut failure = format!(
+                "{} {} / {}",
+                options.failure_symbol(),
+                check.section(),
+                check.name()
+            );
+            if let Some(msg) = r.failure_message {
+                failure.push_str(&format!(": {msg}"));
+            }
+            if let Some(details) = r.details {
+                failure.push_str(&format_details(&details));
+            }
+            Some(failure)
+        })
+        .collect();
+    if !failures.is_empty() {
+        out_line!(out, "Failures\n");
+        for failure in failures {
+            out_line!(out, "{failure}\n");
+        }
+    }
+
+    // Errors found running the checks (these checks are not scored)
+    let errors = report.errors(registry);
+    if !errors.is_empty() {
+        out_line!(out, "Errors\n");
+        for (check_id, err) in errors {
+            let check = registry.get(&check_id).unwrap();
+            out_line!(
+                out,
+                "{} {} / {}: {err}",
+                options.failure_symbol(),
+                check.section(),
+                check.name()
+            );
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Print the score explanation provided.
+pub(crate) fn display_explanation(
+    explanation: &Explanation,
+    registry: &Registry,
+    options: &DisplayOptions,
+) {
+    let check_name = |check_id: &str| match registry.get(check_id) {
+        Some(check) => format!("{} / {}", check.section(), check.name()),
+        None => check_id.to_string(),
+    };
+
+    // Contributions table
+    println!("Score explanation\n");
+    let mut contributions = new_table(options);
+    contributions.set_header(vec![
+        cell_header("Check"),
+        cell_header("Weight"),
+        cell_header("Points"),
+        cell_header("Points lost"),
+    ]);
+    for c in &explanation.checks {
+        contributions.add_row(vec![
+            cell_entry(&check_name(&c.check_id)),
+            Cell::new(c.weight).set_alignment(CellAlignment::Right),
+            cell_points(c.points),
+            cell_points(c.points_lost),
+        ]);
+    }
+    println!("{contributions}\n");
+
+    // Fixes needed to reach the next rating
+    if let Some(next) = &explanation.next_rating {
+        println!(
+            "To reach rating {} (score {} or higher, {:.1} points needed) fix:\n",
+            next.rating.to_ascii_uppercase(),
+            next.min_score,
+            next.points_needed
+        );
+        for c in &next.fixes {
+            println!(
+                "{} {} (+{:.1} points)",
+                options.failure_symbol(),
+                check_name(&c.check_id),
+                c.points_lost
+            );
+        }
+        println!();
+    }
+}
+
+/// Print the changes since the baseline provided.
+pub(crate) fn display_changes(changes: &Changes, registry: &Registry, options: &DisplayOptions) {
+    print!("{}", render_changes(changes, registry, options));
+}
+
+/// Render the changes since the baseline provided, as printed by
+/// display_changes.
+fn render_changes(changes: &Changes, registry: &Registry, options: &DisplayOptions) -> String {
+    let mut out = String::new();
+    out_line!(out, "Changes since baseline\n");
+    out_line!(
+        out,
+        "Global score: {} -> {} ({:+})\n",
+        changes.previous_score.round(),
+        (changes.previous_score + changes.score_delta).round(),
+        changes.score_delta.round()
+    );
+    let check_name = |check_id: &String| match registry.get(check_id) {
+        Some(check) => format!("{} / {}", check.section(), check.name()),
+        None => check_id.clone(),
+    };
+    for check_id in &changes.newly_failing {
+        out_line!(
+            out,
+            "{} Newly failing: {}",
+            options.failure_symbol(),
+            check_name(check_id)
+        );
+    }
+    for check_id in &changes.newly_passing {
+        out_line!(
+            out,
+            "{} Newly passing: {}",
+            options.success_symbol(),
+            check_name(check_id)
+        );
+    }
+    if changes.newly_failing.is_empty() && changes.newly_passing.is_empty() {
+        out_line!(out, "No checks changed");
+    }
+    out.push('\n');
+    out
+}
+
+/// Print the project score, merged from the repositories scores provided.
+pub(crate) fn display_project(
+    repositories: &[RepositoryResults],
+    score: Option<&Score>,
+    profile: &Profile,
+    options: &DisplayOptions,
+) {
+    println!("Project score summary\n");
+    let mut summary = new_table(options);
+    summary.set_header(vec![
+        cell_header("Repository"),
+        cell_header("Score"),
+        cell_header("Rating"),
+    ]);
+    for r in repositories {
+        let (score_cell, rating_cell) = match &r.results {
+            Ok(results) => (
+                cell_score(Some(results.score.global())),
+                cell_rating(Some(profile.rating(results.score.global()))),
+            ),
+            Err(_) => (
+                Cell::new(ERROR_MSG)
+                    .set_alignment(CellAlignment::Center)
+                    .fg(Color::Magenta),
+                cell_rating(None),
+            ),
+        };
+        summary.add_row(vec![cell_entry(&r.name), score_cell, rating_cell]);
+    }
+    summary.add_row(vec![
+        cell_header("Project"),
+        cell_score(score.map(Score::global)),
+        cell_rating(score.map(|score| profile.rating(score.global()))),
+    ]);
+    println!("{summary}\n");
+
+    if let Some(score) = score {
+        let mut sections = new_table(options);
+        sections.set_header(vec![cell_header("Section"), cell_header("Score")]);
+        for section in Section::ALL {
+            sections.add_row(vec![
+                cell_entry(&section.to_string()),
+                cell_score(score.section_score(section)),
+            ]);
+        }
+        println!("{sections}\n");
+    }
+
+    // Errors found linting the repositories (these are not scored)
+    let errors: Vec<String> = repositories
+        .iter()
+        .filter_map(|r| {
+            let err = r.results.as_ref().err()?;
+            Some(format!(
+                "{} {} ({}): {err}",
+                options.failure_symbol(),
+                r.name,
+                r.url
+            ))
+        })
+        .collect();
+    if !errors.is_empty() {
+        println!("Errors\n");
+        for err in errors {
+            println!("{err}");
+        }
+        println!();
+    }
+}
+
+/// Print the built-in checks provided.
+pub(crate) fn display_checks(checks: &[CheckInfo], options: &DisplayOptions) {
+    let mut table = new_table(options);
+    table.set_header(vec![
+        cell_header("ID"),
+        cell_header("Section"),
+        cell_header("Name"),
+        cell_header("Weight"),
+        cell_header("Check sets"),
+    ]);
+    for info in checks {
+        table.add_row(vec![
+            cell_entry(&info.id),
+            cell_entry(&info.section.to_string()),
+            cell_entry(&info.name),
+            cell_weight(Some(info.weight)),
+            cell_entry(&check_set_names(&info.check_sets)),
         ]);
-    println!("{checks}\n");
+    }
+    println!("{table}");
+}
+
+/// Print the details of the built-in check provided.
+pub(crate) fn display_check(info: &CheckInfo) {
+    println!("{} ({})\n", info.title, info.id);
+    println!("{}\n", info.description);
+    println!("Section:     {}", info.section);
+    println!("Weight:      {}", info.weight);
+    println!("Check sets:  {}", check_set_names(&info.check_sets));
+    println!(
+        "Remote only: {}",
+        if info.remote_only { "yes" } else { "no" }
+    );
+    if let Some(docs_url) = &info.docs_url {
+        println!("Docs:        {docs_url}");
+    }
+    if let Some(remediation) = &info.remediation {
+        println!("Remediation: {remediation}");
+    }
+    println!("\nThis check passes if:\n");
+    for c in &info.criteria {
+        println!("- {}", c.description);
+        if c.patterns.is_empty() {
+            continue;
+        }
+        let kind = match c.source {
+            Source::File => "Globs",
+            Source::Repository => "Values",
+            _ => "Regexps",
+        };
+        match c.case_sensitive {
+            Some(true) => println!("  {kind} (case sensitive):"),
+            Some(false) => println!("  {kind} (case insensitive):"),
+            None => println!("  {kind}:"),
+        }
+        for pattern in &c.patterns {
+            println!("    {pattern}");
+        }
+    }
+    println!();
+}
+
+/// Format the value of the check result provided, if any.
+fn format_value(r: &CheckOutput) -> Option<String> {
+    match r.value.as_ref()? {
+        CheckValue::Bool(v) => Some(v.to_string()),
+        CheckValue::String(v) => Some(v.clone()),
+    }
+}
+
+/// Return the url of the evidence supporting the check result provided, or
+/// the url the check reported otherwise.
+fn evidence_url(r: &CheckOutput) -> Option<String> {
+    r.evidence
+        .as_ref()
+        .map(|evidence| evidence.url.clone())
+        .or_else(|| r.url.clone())
+}
+
+/// Format the details of a failed check, one entry per line.
+fn format_details(details: &CheckDetails) -> String {
+    let mut lines = vec![];
+    if !details.globs.is_empty() {
+        lines.push(format!("Files looked for: {}", details.globs.join(", ")));
+    }
+    if !details.readme_regexps.is_empty() {
+        lines.push("README regexps evaluated:".to_string());
+        for re in &details.readme_regexps {
+            lines.push(format!("  {re}"));
+        }
+    }
+    if details.community_repo_checked {
+        lines.push("Organization's .github repository consulted".to_string());
+    }
+    if !details.sources.is_empty() {
+        lines.push(format!("Other sources: {}", details.sources.join(", ")));
+    }
+    if let Some(remediation) = &details.remediation {
+        lines.push(format!("Hint: {remediation}"));
+    }
+    if let Some(docs_url) = &details.docs_url {
+        lines.push(format!("Docs: {docs_url}"));
+    }
+    lines.iter().map(|line| format!("\n    {line}")).collect()
+}
+
+/// Create a new table, setup using the display options provided.
+fn new_table(options: &DisplayOptions) -> Table {
+    let mut table = Table::new();
+    if options.ascii {
+        table.load_preset(ASCII_FULL);
+    } else {
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS);
+    }
+    if options.no_color {
+        table.force_no_tty();
+    }
+    table
 }
 
 /// Build a cell used for headers text.
@@ -166,13 +525,54 @@ fn cell_score(score: Option<f64>) -> Cell {
         .fg(color)
 }
 
-/// Build a cell used for checks results.
-fn cell_check<T>(r: &Option<CheckResult<T>>) -> Cell {
+/// Build a cell used for ratings.
+fn cell_rating(rating: Option<char>) -> Cell {
+    let content = match rating {
+        Some(rating) => rating.to_ascii_uppercase().to_string(),
+        None => NOT_APPLICABLE_MSG.to_string(),
+    };
+    Cell::new(content)
+        .set_alignment(CellAlignment::Center)
+        .add_attribute(Attribute::Bold)
+}
+
+/// Build a cell used for weights.
+fn cell_weight(weight: Option<usize>) -> Cell {
+    match weight {
+        Some(weight) => Cell::new(weight).set_alignment(CellAlignment::Right),
+        None => Cell::new(NOT_APPLICABLE_MSG).set_alignment(CellAlignment::Right),
+    }
+}
+
+/// Build a cell used for points.
+fn cell_points(points: f64) -> Cell {
+    Cell::new(format!("{points:.1}")).set_alignment(CellAlignment::Right)
+}
+
+/// Build a cell used for checks results. Checks that provide a textual value
+/// (i.e. the license SPDX id) display it instead of the result symbol.
+fn cell_check(r: &Option<CheckOutput>, options: &DisplayOptions) -> Cell {
+    if let Some(CheckResult {
+        value: Some(CheckValue::String(value)),
+        ..
+    }) = r
+    {
+        return Cell::new(value)
+            .set_alignment(CellAlignment::Center)
+            .add_attribute(Attribute::Bold);
+    }
+    cell_check_status(r, options)
+}
+
+/// Build a cell used for checks results status.
+fn cell_check_status(r: &Option<CheckOutput>, options: &DisplayOptions) -> Cell {
     let (content, color) = match r {
+        Some(r) if r.not_evaluated => (NOT_EVALUATED_MSG.to_string(), Color::Grey),
+        Some(r) if r.error.is_some() => (ERROR_MSG.to_string(), Color::Magenta),
         Some(r) => match (r.passed, r.exempt) {
-            (true, _) => (SUCCESS_SYMBOL.to_string(), Color::Green),
+            (true, _) => (options.success_symbol().to_string(), Color::Green),
             (false, true) => (EXEMPT_MSG.to_string(), Color::Yellow),
-            (false, _) => (FAILURE_SYMBOL.to_string(), Color::Red),
+            (false, _) => (options.failure_symbol().to_string(), Color::Red),
         },
         None => (NOT_APPLICABLE_MSG.to_string(), Color::Grey),
     };
@@ -181,3 +581,45 @@ fn cell_check<T>(r: &Option<CheckResult<T>>) -> Cell {
         .add_attribute(Attribute::Bold)
         .fg(color)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn ascii_output_contains_only_ascii_characters() {
+        let mut report = Report::default();
+        report.documentation.readme =
+            Some(serde_json::from_value(json!({"passed": true, "exempt": false})).unwrap());
+        report.documentation.adopters =
+            Some(serde_json::from_value(json!({"passed": false, "exempt": false})).unwrap());
+        report.license.spdx_id = Some(
+            serde_json::from_value(json!({"passed": false, "exempt": false, "error": "boom"}))
+                .unwrap(),
+        );
+        let registry = Registry::default();
+        let profile = Profile::default();
+        let score = score::calculate(&report, &registry, &profile);
+        let changes = Changes {
+            newly_failing: vec!["adopters".to_string()],
+            newly_passing: vec!["readme".to_string()],
+            previous_score: 100.0,
+            score_delta: score.global() - 100.0,
+        };
+
+        for verbose in [false, true] {
+            let options = DisplayOptions {
+                verbose,
+                no_color: true,
+                ascii: true,
+            };
+            let out = render(&report, &score, &registry, &profile, &options)
+                + &render_changes(&changes, &registry, &options);
+            assert!(out.contains(ASCII_SUCCESS_SYMBOL));
+            assert!(out.contains(ASCII_FAILURE_SYMBOL));
+            assert!(out.contains("Errors"));
+            assert!(out.is_ascii(), "{out}");
+        }
+    }
+}
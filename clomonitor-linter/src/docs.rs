@@ -0,0 +1,140 @@
+use anyhow::{format_err, Error};
+use clap::ArgEnum;
+use clomonitor_core::linter::{CheckInfo, CheckSet, Section, Source};
+use std::{fmt::Write, slice};
+
+/// Markers delimiting the check sets block in the checks documentation.
+const CHECK_SETS_MARKERS: (&str, &str) = ("<!-- check-sets:start -->", "<!-- check-sets:end -->");
+
+/// Markers delimiting the checks reference block in the checks documentation.
+const REFERENCE_MARKERS: (&str, &str) = ("<!-- checks:start -->", "<!-- checks:end -->");
+
+/// Return the names of the check sets provided, separated by commas.
+pub(crate) fn check_set_names(check_sets: &[CheckSet]) -> String {
+    check_sets
+        .iter()
+        .filter_map(|check_set| Some(check_set.to_possible_value()?.get_name().to_string()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Return a hint about the kind of repositories the check set provided is
+/// recommended for.
+fn check_set_hint(check_set: &CheckSet) -> &'static str {
+    match check_set {
+        CheckSet::Code => "recommended for projects' primary code repository",
+        CheckSet::CodeLite => "subset of *code*, recommended for secondary code repositories",
+        CheckSet::Community => "recommended for repositories with community content",
+        CheckSet::Docs => "recommended for other documentation repositories",
+    }
+}
+
+/// Replace the generated blocks in the checks documentation provided with
+/// the ones rendered from the checks catalog.
+pub(crate) fn update(content: &str, checks: &[CheckInfo]) -> Result<String, Error> {
+    let content = replace_block(content, CHECK_SETS_MARKERS, &render_check_sets(checks))?;
+    replace_block(&content, REFERENCE_MARKERS, &render_reference(checks))
+}
+
+/// Render the checks included in each of the check sets.
+pub(crate) fn render_check_sets(checks: &[CheckInfo]) -> String {
+    let mut md = String::new();
+    for check_set in CheckSet::value_variants() {
+        let _ = writeln!(
+            md,
+            "- **{}** ({})\n",
+            check_set_names(slice::from_ref(check_set)),
+            check_set_hint(check_set)
+        );
+        for info in checks.iter().filter(|c| c.check_sets.contains(check_set)) {
+            let _ = writeln!(md, "  - {} / {}", info.section, info.name);
+        }
+        let _ = writeln!(md);
+    }
+    md.trim_end().to_string()
+}
+
+/// Render the checks reference, grouped by section.
+pub(crate) fn render_reference(checks: &[CheckInfo]) -> String {
+    let mut md = String::new();
+    for section in Section::ALL {
+        let _ = writeln!(md, "## {section}\n");
+        for info in checks.iter().filter(|c| c.section == section) {
+            let _ = writeln!(md, "### {}\n", info.title);
+            let _ = writeln!(md, "**ID**: `{}`\n", info.id);
+            let _ = writeln!(md, "{}\n", info.description);
+            let _ = writeln!(md, "This check passes if:\n");
+            for c in &info.criteria {
+                if c.patterns.is_empty() {
+                    let _ = writeln!(md, "- {}\n", c.description);
+                    continue;
+                }
+                match c.source {
+                    Source::File => {
+                        let _ = writeln!(md, "- {} Globs used:\n", c.description);
+                    }
+                    Source::Readme | Source::Remote => {
+                        let _ = writeln!(md, "- {} Regexps used:\n", c.description);
+                    }
+                    Source::Repository => {
+                        let _ = writeln!(md, "- {}\n", c.description);
+                    }
+                }
+                let _ = writeln!(md, "```sh");
+                for pattern in &c.patterns {
+                    let _ = writeln!(md, "\"{pattern}\"");
+                }
+                if let Some(case_sensitive) = c.case_sensitive {
+                    let _ = writeln!(md, "\nCASE SENSITIVE: {case_sensitive}");
+                }
+                let _ = writeln!(md, "```\n");
+            }
+        }
+    }
+    md.trim_end().to_string()
+}
+
+/// Replace the content between the markers provided with the block given.
+fn replace_block(content: &str, markers: (&str, &str), block: &str) -> Result<String, Error> {
+    let (start_marker, end_marker) = markers;
+    let start = content
+        .find(start_marker)
+        .ok_or_else(|| format_err!("marker not found: {start_marker}"))?
+        + start_marker.len();
+    let end = content[start..]
+        .find(end_marker)
+        .ok_or_else(|| format_err!("marker not found: {end_marker}"))?
+        + start;
+    Ok(format!(
+        "{}\n\n{block}\n\n{}",
+        &content[..start],
+        &content[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clomonitor_core::linter::catalog;
+    use std::fs;
+
+    #[test]
+    fn checks_docs_are_up_to_date() {
+        let content = fs::read_to_string("../docs/checks.md").unwrap();
+        assert_eq!(
+            update(&content, &catalog()).unwrap(),
+            content,
+            "docs/checks.md is out of date, please run: clomonitor-linter checks docs --update docs/checks.md"
+        );
+    }
+
+    #[test]
+    fn replace_block_between_markers() {
+        let content = "intro\n<!-- checks:start -->\nold\n<!-- checks:end -->\noutro\n";
+        assert_eq!(
+            replace_block(content, REFERENCE_MARKERS, "new").unwrap(),
+            "intro\n<!-- checks:start -->\n\nnew\n\n<!-- checks:end -->\noutro\n"
+        );
+        assert!(replace_block("intro", REFERENCE_MARKERS, "new").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    display::{ERROR_MSG, EXEMPT_MSG, NOT_EVALUATED_MSG},
+    docs::check_set_names,
+};
+use anyhow::Error;
+use askama::Template;
+use chrono::{DateTime, Utc};
+use clomonitor_core::{
+    linter::{CheckOutput, CheckSet, Registry, Report, Section},
+    score::{Profile, Score},
+};
+
+/// Information about the linter run included in the report.
+pub(crate) struct Metadata<'a> {
+    pub url: &'a str,
+    pub commit: Option<String>,
+    pub generated_at: DateTime<Utc>,
+    pub check_sets: &'a [CheckSet],
+}
+
+/// Template for the standalone HTML report.
+#[derive(Template)]
+#[template(path = "report.html")]
+struct ReportTemplate<'a> {
+    url: &'a str,
+    commit: Option<String>,
+    generated_at: String,
+    check_sets: String,
+    score: &'a Score,
+    rating: char,
+    pass_score: f64,
+    passed: bool,
+    license: Option<String>,
+    sections: Vec<SectionRow>,
+    checks: Vec<CheckRow>,
+}
+
+/// Section entry in the report summary.
+struct SectionRow {
+    name: String,
+    score: Option<f64>,
+    rating: Option<char>,
+}
+
+/// Check entry in the report checks table.
+struct CheckRow {
+    section: String,
+    name: String,
+    status: &'static str,
+    status_label: &'static str,
+    info: Option<String>,
+    link: Option<String>,
+    docs_url: Option<String>,
+}
+
+/// Render a self-contained HTML report of the linter results provided.
+pub(crate) fn render(
+    report: &Report,
+    score: &Score,
+    registry: &Registry,
+    profile: &Profile,
+    pass_score: f64,
+    passed: bool,
+    metadata: Metadata,
+) -> Result<String, Error> {
+    let sections = Section::ALL
+        .iter()
+        .map(|section| {
+            let section_score = score.section_score(*section);
+            SectionRow {
+                name: section.to_string(),
+                score: section_score,
+                rating: section_score.map(|v| profile.rating(v)),
+            }
+        })
+        .collect();
+    let checks = registry
+        .iter()
+        .filter_map(|check| {
+            let r = report.get(check.id())?;
+            let (status, status_label, info) = check_status(&r);
+            Some(CheckRow {
+                section: check.section().to_string(),
+                name: check.name().to_string(),
+                status,
+                status_label,
+                info,
+                link: r.evidence.as_ref().map(|e| e.url.clone()).or(r.url),
+                docs_url: check.docs_url(),
+            })
+        })
+        .collect();
+    let template = ReportTemplate {
+        url: metadata.url,
+        commit: metadata.commit,
+        generated_at: metadata
+            .generated_at
+            .format("%Y-%m-%d %H:%M:%S UTC")
+            .to_string(),
+        check_sets: check_set_names(metadata.check_sets),
+        score,
+        rating: profile.rating(score.global()),
+        pass_score,
+        passed,
+        license: detected_license(report),
+        sections,
+        checks,
+    };
+    Ok(template.render()?)
+}
+
+/// Return the status of the check result provided and its label, along with
+/// the information to display next to it (exemption reason, failure message
+/// or error).
+fn check_status(r: &CheckOutput) -> (&'static str, &'static str, Option<String>) {
+    if r.not_evaluated {
+        ("not-evaluated", NOT_EVALUATED_MSG, None)
+    } else if let Some(err) = &r.error {
+        ("error", ERROR_MSG, Some(err.clone()))
+    } else if r.passed {
+        ("passed", "Passed", None)
+    } else if r.exempt {
+        ("exempt", EXEMPT_MSG, r.exemption_reason.clone())
+    } else {
+        ("failed", "Failed", r.failure_message.clone())
+    }
+}
+
+/// Return the SPDX id of the license detected, if any.
+fn detected_license(report: &Report) -> Option<String> {
+    report.license.spdx_id.as_ref()?.value.clone()
+}
+
+mod filters {
+    /// Template filter that returns the width of the section score bar.
+    pub fn rs_section_score_width(score: &Option<f64>) -> ::askama::Result<f64> {
+        Ok(clomonitor_core::score::section_bar_width(*score))
+    }
+
+    /// Template filter that returns the integer part of the rounded score
+    /// value provided as a string. "n/a" is returned when the value is none.
+    pub fn to_string(score: &Option<f64>) -> ::askama::Result<String> {
+        Ok(match score {
+            Some(v) => (v.round() as usize).to_string(),
+            None => "n/a".to_string(),
+        })
+    }
+
+    /// Template filter that returns the rating letter provided as a string.
+    /// "na" is returned when the rating is none.
+    pub fn rating_opt(rating: &Option<char>) -> ::askama::Result<String> {
+        Ok(match rating {
+            Some(rating) => rating.to_string(),
+            None => "na".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use clomonitor_core::score;
+    use serde_json::json;
+
+    #[test]
+    fn render_report() {
+        let mut report = Report::default();
+        report.documentation.readme = Some(
+            serde_json::from_value(json!({
+                "passed": true,
+                "exempt": false,
+                "evidence": {
+                    "file": "README.md",
+                    "line": 1,
+                    "snippet": "",
+                    "url": "https://github.com/org/repo/blob/main/README.md#L1",
+                },
+            }))
+            .unwrap(),
+        );
+        report.documentation.adopters = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": true,
+                "exemption_reason": "<not> applicable",
+            }))
+            .unwrap(),
+        );
+        report.license.spdx_id = Some(
+            serde_json::from_value(json!({
+                "passed": true,
+                "exempt": false,
+                "value": "Apache-2.0",
+            }))
+            .unwrap(),
+        );
+        let registry = Registry::default();
+        let profile = Profile::default();
+        let score = score::calculate(&report, &registry, &profile);
+
+        let html = render(
+            &report,
+            &score,
+            &registry,
+            &profile,
+            80.0,
+            true,
+            Metadata {
+                url: "https://github.com/org/repo",
+                commit: Some("0123456789abcdef".to_string()),
+                generated_at: Utc.ymd(2022, 3, 1).and_hms(10, 0, 0),
+                check_sets: &[CheckSet::Code, CheckSet::CodeLite],
+            },
+        )
+        .unwrap();
+        assert!(html.contains("0123456789abcdef"));
+        assert!(html.contains("2022-03-01 10:00:00 UTC"));
+        assert!(html.contains("code, code-lite"));
+        assert!(html.contains("Apache-2.0"));
+        assert!(html.contains(r#"class="bar bar-a" style="width: 106px""#));
+        assert!(html.contains(r#"class="bar bar-na" style="width: 0px""#));
+        assert!(html.contains("README.md#L1"));
+        assert!(html.contains("&lt;not&gt; applicable"));
+    }
+}
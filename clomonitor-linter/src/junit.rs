@@ -0,0 +1,194 @@
+use clomonitor_core::{
+    linter::{Check, CheckOutput, Registry, Report, Section},
+    score::Score,
+};
+use std::fmt::Write;
+
+/// Render the linter report provided as a JUnit XML document. Each section
+/// becomes a testsuite (holding the section score and weight as properties)
+/// and each check a testcase. Checks not applicable are omitted.
+pub(crate) fn render(report: &Report, score: &Score, registry: &Registry) -> String {
+    let mut suites = String::new();
+    let (mut total_tests, mut total_failures, mut total_errors, mut total_skipped) = (0, 0, 0, 0);
+    for section in Section::ALL {
+        let results: Vec<(&dyn Check, CheckOutput)> = registry
+            .section(section)
+            .filter_map(|check| Some((check, report.get(check.id())?)))
+            .collect();
+        if results.is_empty() {
+            continue;
+        }
+
+        let mut cases = String::new();
+        let (mut failures, mut errors, mut skipped) = (0, 0, 0);
+        for (check, r) in &results {
+            let (case, outcome) = testcase(*check, r);
+            match outcome {
+                Outcome::Failure => failures += 1,
+                Outcome::Error => errors += 1,
+                Outcome::Skipped => skipped += 1,
+                Outcome::Passed => {}
+            }
+            cases.push_str(&case);
+        }
+
+        let mut properties = String::new();
+        if let Some(section_score) = score.section_score(section) {
+            property(&mut properties, "score", &section_score.round().to_string());
+        }
+        if let Some(section_weight) = score.section_weight(section) {
+            property(&mut properties, "weight", &section_weight.to_string());
+        }
+        let _ = writeln!(
+            suites,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\">",
+            escape(&section.to_string()),
+            results.len(),
+        );
+        if !properties.is_empty() {
+            let _ = writeln!(suites, "    <properties>\n{properties}    </properties>");
+        }
+        suites.push_str(&cases);
+        suites.push_str("  </testsuite>\n");
+
+        total_tests += results.len();
+        total_failures += failures;
+        total_errors += errors;
+        total_skipped += skipped;
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" tests=\"{total_tests}\" failures=\"{total_failures}\" errors=\"{total_errors}\" skipped=\"{total_skipped}\">\n{suites}</testsuites>",
+        env!("CARGO_PKG_NAME"),
+    )
+}
+
+/// Outcome of a testcase.
+enum Outcome {
+    Passed,
+    Failure,
+    Error,
+    Skipped,
+}
+
+/// Build the testcase element for the check result provided.
+fn testcase(check: &dyn Check, r: &CheckOutput) -> (String, Outcome) {
+    let open = format!(
+        "    <testcase name=\"{}\" classname=\"{}\">",
+        escape(check.id()),
+        escape(&check.section().to_string()),
+    );
+    let (body, outcome) = if r.passed {
+        (String::new(), Outcome::Passed)
+    } else if r.exempt {
+        let reason = r.exemption_reason.as_deref().unwrap_or("exempt");
+        (
+            format!("<skipped message=\"{}\"/>", escape(reason)),
+            Outcome::Skipped,
+        )
+    } else if r.not_evaluated {
+        (
+            "<skipped message=\"not evaluated\"/>".to_string(),
+            Outcome::Skipped,
+        )
+    } else if let Some(err) = &r.error {
+        (
+            format!("<error message=\"{}\"/>", escape(err)),
+            Outcome::Error,
+        )
+    } else {
+        let message = match &r.failure_message {
+            Some(msg) => msg.clone(),
+            None => format!("{} check failed", check.name()),
+        };
+        let mut text = vec![];
+        if let Some(details) = &r.details {
+            if let Some(remediation) = &details.remediation {
+                text.push(remediation.clone());
+            }
+            if let Some(docs_url) = &details.docs_url {
+                text.push(docs_url.clone());
+            }
+        }
+        (
+            format!(
+                "<failure message=\"{}\">{}</failure>",
+                escape(&message),
+                escape(&text.join("\n"))
+            ),
+            Outcome::Failure,
+        )
+    };
+    (format!("{open}{body}</testcase>\n"), outcome)
+}
+
+/// Append a property element with the name and value provided.
+fn property(properties: &mut String, name: &str, value: &str) {
+    let _ = writeln!(
+        properties,
+        "      <property name=\"{}\" value=\"{}\"/>",
+        escape(name),
+        escape(value)
+    );
+}
+
+/// Escape the text provided so that it can be used in XML content and
+/// attributes.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clomonitor_core::score::{self, Profile};
+    use serde_json::json;
+
+    #[test]
+    fn render_sections_and_checks() {
+        let mut report = Report::default();
+        report.documentation.adopters = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": false,
+                "failure_message": "adopters <file> not found",
+            }))
+            .unwrap(),
+        );
+        report.documentation.readme = Some(true.into());
+        report.security.sbom = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": true,
+                "exemption_reason": "not applicable",
+            }))
+            .unwrap(),
+        );
+        let registry = Registry::default();
+        let score = score::calculate(&report, &registry, &Profile::default());
+
+        let xml = render(&report, &score, &registry);
+        assert!(xml.contains(
+            "<testsuites name=\"clomonitor-linter\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\">"
+        ));
+        assert!(xml.contains(
+            "<testsuite name=\"Documentation\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\">"
+        ));
+        assert!(xml.contains("<property name=\"score\" value=\"91\"/>"));
+        assert!(xml.contains("<failure message=\"adopters &lt;file&gt; not found\">"));
+        assert!(xml.contains("<skipped message=\"not applicable\"/>"));
+        assert!(!xml.contains("name=\"License\""));
+    }
+}
@@ -1,62 +1,571 @@
 use anyhow::{format_err, Error};
-use clap::Parser;
+use chrono::Utc;
+use clap::{Parser, Subcommand};
 use clomonitor_core::{
-    linter::{lint, CheckSet, LintOptions},
-    score,
+    git::{self, Progress, ProgressCallback, RemoteOptions},
+    linter::{catalog, lint, CheckSet, LintOptions, Registry, Section, METADATA_FILE},
+    score::{self, Profile, Score},
 };
 use display::*;
-use std::path::PathBuf;
+use output::{Format, Output, SCHEMA_VERSION};
+use policy::{Policy, DEFAULT_PASS_SCORE};
+use project::Manifest;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tempdir::TempDir;
 
+mod baseline;
 mod display;
+mod docs;
+mod html;
+mod junit;
+mod markdown;
+mod output;
+mod policy;
+mod project;
+mod sarif;
 
 #[derive(Debug, Parser)]
-#[clap(author, version, about)]
+#[clap(
+    author,
+    version,
+    about,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Args {
-    /// Repository root path
-    #[clap(long, parse(from_os_str), default_value = ".")]
-    path: PathBuf,
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Repository root path. When not provided, the repository (default branch or reference) is cloned (shallow) from the url into a temporary directory
+    #[clap(long, parse(from_os_str))]
+    path: Option<PathBuf>,
+
+    /// Branch or tag to lint when cloning the repository
+    #[clap(long = "ref", conflicts_with = "path")]
+    reference: Option<String>,
+
+    /// Project manifest listing the repositories to lint. The project score is calculated by merging the repositories scores
+    #[clap(
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &[
+            "path",
+            "reference",
+            "url",
+            "policy",
+            "require",
+            "min-documentation",
+            "min-license",
+            "min-best-practices",
+            "min-security",
+            "min-legal",
+            "baseline",
+        ]
+    )]
+    project: Option<PathBuf>,
 
     /// Sets of checks to run
     #[clap(arg_enum, long, default_values = &["code", "community"])]
     check_set: Vec<CheckSet>,
 
-    /// Linter pass score
-    #[clap(long, default_value = "80")]
-    pass_score: f64,
+    /// Linter pass score [default: 80]
+    #[clap(long)]
+    pass_score: Option<f64>,
+
+    /// Minimum documentation section score
+    #[clap(long)]
+    min_documentation: Option<f64>,
+
+    /// Minimum license section score
+    #[clap(long)]
+    min_license: Option<f64>,
+
+    /// Minimum best practices section score
+    #[clap(long)]
+    min_best_practices: Option<f64>,
+
+    /// Minimum security section score
+    #[clap(long)]
+    min_security: Option<f64>,
+
+    /// Minimum legal section score
+    #[clap(long)]
+    min_legal: Option<f64>,
+
+    /// Checks that must pass (or be exempt) regardless of the score
+    #[clap(long)]
+    require: Vec<String>,
+
+    /// Policy file (pass score, sections minimum scores and required checks). Options provided in the command line take precedence
+    #[clap(long, parse(from_os_str))]
+    policy: Option<PathBuf>,
+
+    /// Repository url [https://github.com/org/repo or https://gitlab.com/org/repo] (required for some remote checks)
+    #[clap(long, required_unless_present = "project")]
+    url: Option<String>,
+
+    /// Run only the checks that do not require network access (remote checks are reported as not evaluated)
+    #[clap(long)]
+    offline: bool,
+
+    /// Scoring profile file (per-check weights, section weights and rating thresholds)
+    #[clap(long, parse(from_os_str))]
+    profile: Option<PathBuf>,
 
-    /// Repository url [https://github.com/org/repo] (required for some GitHub remote checks)
+    /// Explain the score, including the fixes needed to reach the next rating
     #[clap(long)]
-    url: String,
+    explain: bool,
+
+    /// Output format
+    #[clap(arg_enum, long, default_value = "table")]
+    format: Format,
+
+    /// Display additional details in the tables (evidence url, exemption reason, weight and points of each check, and sections weights)
+    #[clap(long)]
+    verbose: bool,
+
+    /// Do not use colors when displaying the tables
+    #[clap(long)]
+    no_color: bool,
+
+    /// Use plain ASCII characters to draw the tables (useful for logs)
+    #[clap(long)]
+    ascii: bool,
+
+    /// Previous JSON report to compare the results with. When provided, the linter fails only if a check that used to pass now fails or the global score drops more than the tolerance (the pass score is ignored)
+    #[clap(long, parse(from_os_str))]
+    baseline: Option<PathBuf>,
+
+    /// Global score points drop allowed when comparing with a baseline
+    #[clap(long, default_value = "0")]
+    tolerance: f64,
+
+    /// Custom checks file (same format as the checks section of .clomonitor.yml)
+    #[clap(long, parse(from_os_str))]
+    checks_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Inspect the built-in checks
+    #[clap(subcommand)]
+    Checks(ChecksCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum ChecksCommand {
+    /// List the built-in checks
+    List {
+        /// Display only the checks in this check set
+        #[clap(arg_enum, long)]
+        check_set: Option<CheckSet>,
+    },
+
+    /// Describe a built-in check (what it looks for, weight, check sets, etc)
+    Describe {
+        /// Check identifier
+        check_id: String,
+    },
+
+    /// Render the checks reference documentation (markdown)
+    Docs {
+        /// Checks documentation file to update (blocks between the generation markers are replaced)
+        #[clap(long, parse(from_os_str))]
+        update: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let args = Args::parse();
+    if let Some(Command::Checks(command)) = &args.command {
+        return run_checks_command(command, &args);
+    }
+
+    // Check the custom checks file and load the scoring profile (we want to
+    // fail early if they are not valid)
+    if let Some(checks_file) = &args.checks_file {
+        if !checks_file.exists() {
+            return Err(format_err!(
+                "checks file not found: {}",
+                checks_file.display()
+            ));
+        }
+    }
+    let profile = match &args.profile {
+        Some(path) => Profile::from_file(path)?,
+        None => Profile::default(),
+    };
+
+    // Lint all the project's repositories when a manifest is provided
+    if let Some(manifest) = &args.project {
+        return lint_project(&args, manifest, &profile).await;
+    }
+    let url = args.url.clone().unwrap_or_default();
+
+    // Clone the repository when no local path has been provided
+    let tmp_dir;
+    let root = match &args.path {
+        Some(path) => path.clone(),
+        None => {
+            tmp_dir = TempDir::new("clomonitor")?;
+            git::clone(
+                &url,
+                tmp_dir.path(),
+                args.reference.as_deref(),
+                &clone_options(&args, &url),
+            )
+            .await
+            .map_err(|err| format_err!("error cloning repository {url}: {err}"))?;
+            if args.format == Format::Table {
+                eprintln!();
+            }
+            tmp_dir.path().to_path_buf()
+        }
+    };
+
+    // Setup checks registry, including any custom checks declared
+    let mut registry = Registry::default();
+    if let Some(checks_file) = &args.checks_file {
+        registry.register_custom_checks(checks_file)?;
+    }
+    registry.register_custom_checks(root.join(METADATA_FILE))?;
+
+    // Setup policy from the policy file and the command line options
+    let mut policy = match &args.policy {
+        Some(path) => Policy::from_file(path)?,
+        None => Policy::default(),
+    };
+    if args.pass_score.is_some() {
+        policy.pass_score = args.pass_score;
+    }
+    for (section, min_score) in [
+        (Section::Documentation, args.min_documentation),
+        (Section::License, args.min_license),
+        (Section::BestPractices, args.min_best_practices),
+        (Section::Security, args.min_security),
+        (Section::Legal, args.min_legal),
+    ] {
+        if let Some(min_score) = min_score {
+            policy.min_scores.insert(section, min_score);
+        }
+    }
+    for check_id in &args.require {
+        if !policy.require.contains(check_id) {
+            policy.require.push(check_id.clone());
+        }
+    }
+    policy.validate(&registry)?;
+
+    // Load baseline report if provided
+    let baseline = match &args.baseline {
+        Some(path) => Some(baseline::load(path)?),
+        None => None,
+    };
 
     // Lint repository provided and display results
-    println!("\nRunning CLOMonitor linter...\n");
+    if args.format == Format::Table {
+        println!("\nRunning CLOMonitor linter...\n");
+    }
     let options = LintOptions {
-        check_sets: args.check_set,
-        root: args.path,
-        url: args.url,
-        github_token: None,
-    };
-    let report = lint(options).await?;
-    let score = score::calculate(&report);
-    display(&report, &score);
-
-    // Check if the linter succeeded acording to the provided pass score
-    if score.global() >= args.pass_score {
-        println!(
-            "{SUCCESS_SYMBOL} Succeeded with a global score of {}\n",
-            score.global().round()
-        );
-        Ok(())
-    } else {
-        Err(format_err!(
-            "{FAILURE_SYMBOL} Failed with a global score of {} (pass score is {})\n",
-            score.global().round(),
-            args.pass_score
-        ))
+        check_sets: args.check_set.clone(),
+        root: root.clone(),
+        url: url.clone(),
+        offline: args.offline,
+        github_token: env::var("GITHUB_TOKEN").ok(),
+        gitlab_token: env::var("GITLAB_TOKEN").ok(),
+    };
+    let report = lint(options, &registry).await?;
+    let score = score::calculate(&report, &registry, &profile);
+    let explanation = match args.explain {
+        true => Some(score::explain(&report, &registry, &profile)),
+        false => None,
+    };
+    let changes = baseline
+        .as_ref()
+        .map(|baseline| baseline::compare(&report, baseline, &registry, &profile));
+    let violations = policy.evaluate(&report, &score, changes.is_none());
+    let regressed = changes
+        .as_ref()
+        .map_or(false, |changes| changes.regressed(args.tolerance));
+    let passed = violations.is_empty() && !regressed;
+    match args.format {
+        Format::Table => {
+            let options = display_options(&args);
+            display(&report, &score, &registry, &profile, &options);
+            if let Some(explanation) = &explanation {
+                display_explanation(explanation, &registry, &options);
+            }
+            if let Some(changes) = &changes {
+                display_changes(changes, &registry, &options);
+            }
+        }
+        Format::Sarif => println!("{}", sarif::render(&report, &registry, &root)?),
+        Format::Junit => println!("{}", junit::render(&report, &score, &registry)),
+        Format::Markdown => {
+            println!(
+                "{}",
+                markdown::render(
+                    &report,
+                    &score,
+                    &registry,
+                    &profile,
+                    policy.pass_score(),
+                    passed,
+                    changes.as_ref()
+                )
+            );
+        }
+        Format::Html => {
+            let metadata = html::Metadata {
+                url: &url,
+                commit: git::head_commit(&root).ok(),
+                generated_at: Utc::now(),
+                check_sets: &args.check_set,
+            };
+            println!(
+                "{}",
+                html::render(
+                    &report,
+                    &score,
+                    &registry,
+                    &profile,
+                    policy.pass_score(),
+                    passed,
+                    metadata
+                )?
+            );
+        }
+        format => {
+            let output = Output {
+                schema_version: SCHEMA_VERSION,
+                report: &report,
+                score: &score,
+                rating: profile.rating(score.global()),
+                pass_score: policy.pass_score(),
+                passed,
+                explanation: explanation.as_ref(),
+                baseline: changes.as_ref(),
+                violations: &violations,
+            };
+            println!("{}", output::render(&output, format)?);
+        }
+    }
+
+    // Check if the linter succeeded acording to the policy (and if there were
+    // no regressions when comparing with a baseline)
+    if passed {
+        if args.format == Format::Table {
+            println!(
+                "{} Succeeded with a global score of {}\n",
+                display_options(&args).success_symbol(),
+                score.global().round()
+            );
+        }
+        return Ok(());
+    }
+    let mut reasons: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    if let Some(changes) = changes.as_ref().filter(|_| regressed) {
+        reasons.push(format!(
+            "{} checks newly failing and a global score delta of {:+} (tolerance is {})",
+            changes.newly_failing.len(),
+            changes.score_delta.round(),
+            args.tolerance
+        ));
+    }
+    Err(format_err!(
+        "{} Failed with a global score of {}:\n{}\n",
+        display_options(&args).failure_symbol(),
+        score.global().round(),
+        reasons
+            .iter()
+            .map(|reason| format!("  - {reason}"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    ))
+}
+
+/// Lint all the repositories listed in the project manifest provided. The
+/// linter succeeds when all of them were linted and the project score,
+/// merged from the repositories scores, reaches the pass score.
+async fn lint_project(args: &Args, manifest: &Path, profile: &Profile) -> Result<(), Error> {
+    if !matches!(args.format, Format::Table | Format::Json | Format::Yaml) {
+        return Err(format_err!(
+            "only the table, json and yaml formats are supported when linting a project"
+        ));
+    }
+    let manifest = Manifest::from_file(manifest)?;
+    let pass_score = args.pass_score.unwrap_or(DEFAULT_PASS_SCORE);
+
+    // Lint repositories and merge their scores
+    if args.format == Format::Table {
+        println!("\nRunning CLOMonitor linter...\n");
+    }
+    let options = project::Options {
+        checks_file: args.checks_file.as_deref(),
+        profile,
+        offline: args.offline,
+    };
+    let repositories = project::lint_all(&manifest, &options).await;
+    let score = project::project_score(&repositories);
+    let explanation = match args.explain {
+        true => Some(project::project_explanation(&repositories, profile)),
+        false => None,
+    };
+    let errors = repositories.iter().filter(|r| r.results.is_err()).count();
+    let score_passed = score.as_ref().map_or(false, |s| s.global() >= pass_score);
+    let passed = errors == 0 && score_passed;
+
+    // Display results
+    match args.format {
+        Format::Table => {
+            let options = display_options(args);
+            for r in &repositories {
+                println!("Repository {} ({})\n", r.name, r.url);
+                match &r.results {
+                    Ok(results) => display(
+                        &results.report,
+                        &results.score,
+                        &results.registry,
+                        profile,
+                        &options,
+                    ),
+                    Err(err) => println!(
+                        "{} Error linting repository: {err}\n",
+                        options.failure_symbol()
+                    ),
+                }
+            }
+            display_project(&repositories, score.as_ref(), profile, &options);
+            if let Some(explanation) = &explanation {
+                let mut registry = Registry::default();
+                if let Some(checks_file) = &args.checks_file {
+                    registry.register_custom_checks(checks_file)?;
+                }
+                display_explanation(explanation, &registry, &options);
+            }
+        }
+        format => {
+            let output = project::Output {
+                schema_version: SCHEMA_VERSION,
+                name: manifest.name.as_deref(),
+                repositories: repositories
+                    .iter()
+                    .map(|r| project::RepositoryOutput::new(r, profile))
+                    .collect(),
+                score: score.as_ref(),
+                rating: score.as_ref().map(|s| profile.rating(s.global())),
+                pass_score,
+                passed,
+                explanation: explanation.as_ref(),
+            };
+            println!("{}", output::render(&output, format)?);
+        }
+    }
+
+    // Check if the linter succeeded
+    let global = score.as_ref().map_or(0.0, Score::global).round();
+    if passed {
+        if args.format == Format::Table {
+            println!(
+                "{} Succeeded with a project score of {global}\n",
+                display_options(args).success_symbol()
+            );
+        }
+        return Ok(());
+    }
+    let mut reasons = vec![];
+    if errors > 0 {
+        reasons.push(format!("{errors} repositories could not be linted"));
+    }
+    if !score_passed {
+        reasons.push(format!(
+            "project score {global} is lower than the pass score {pass_score}"
+        ));
+    }
+    Err(format_err!(
+        "{} Failed with a project score of {global}:\n{}\n",
+        display_options(args).failure_symbol(),
+        reasons
+            .iter()
+            .map(|reason| format!("  - {reason}"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    ))
+}
+
+/// Run the checks subcommand provided.
+fn run_checks_command(command: &ChecksCommand, args: &Args) -> Result<(), Error> {
+    let checks = catalog();
+    match command {
+        ChecksCommand::List { check_set } => {
+            let checks: Vec<_> = checks
+                .into_iter()
+                .filter(|c| {
+                    check_set
+                        .as_ref()
+                        .map_or(true, |cs| c.check_sets.contains(cs))
+                })
+                .collect();
+            display_checks(&checks, &display_options(args));
+        }
+        ChecksCommand::Describe { check_id } => {
+            let info = checks
+                .iter()
+                .find(|c| &c.id == check_id)
+                .ok_or_else(|| format_err!("check not found: {check_id}"))?;
+            display_check(info);
+        }
+        ChecksCommand::Docs { update: Some(path) } => {
+            let content = fs::read_to_string(path)?;
+            fs::write(path, docs::update(&content, &checks)?)?;
+        }
+        ChecksCommand::Docs { update: None } => {
+            println!("{}\n", docs::render_check_sets(&checks));
+            println!("{}", docs::render_reference(&checks));
+        }
+    }
+    Ok(())
+}
+
+/// Prepare the options used to clone the repository, reporting the progress
+/// when the results are displayed in a table.
+fn clone_options(args: &Args, url: &str) -> RemoteOptions {
+    let progress: Option<ProgressCallback> = match args.format {
+        Format::Table => Some(Arc::new(|p: &Progress| {
+            eprint!(
+                "\rCloning repository: {}/{} objects received",
+                p.received_objects, p.total_objects
+            );
+        })),
+        _ => None,
+    };
+    RemoteOptions {
+        progress,
+        ..project::clone_options(url)
+    }
+}
+
+/// Build the display options from the arguments provided.
+fn display_options(args: &Args) -> DisplayOptions {
+    DisplayOptions {
+        verbose: args.verbose,
+        no_color: args.no_color,
+        ascii: args.ascii,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn args_are_valid() {
+        Args::command().debug_assert();
     }
 }
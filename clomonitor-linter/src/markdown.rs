@@ -0,0 +1,227 @@
+use crate::baseline::Changes;
+use clomonitor_core::{
+    linter::{CheckOutput, Registry, Report, Section},
+    score::{Profile, Score},
+};
+use std::fmt::Write;
+
+const PASSED_ICON: &str = "✅";
+const FAILED_ICON: &str = "❌";
+const EXEMPT_ICON: &str = "➖";
+const NOT_EVALUATED_ICON: &str = "❔";
+const ERROR_ICON: &str = "⚠️";
+
+/// Render a compact Markdown summary of the linter results, suitable to be
+/// posted as a pull request comment. A block with the changes since the
+/// baseline is included when provided.
+pub(crate) fn render(
+    report: &Report,
+    score: &Score,
+    registry: &Registry,
+    profile: &Profile,
+    pass_score: f64,
+    passed: bool,
+    changes: Option<&Changes>,
+) -> String {
+    let mut md = String::new();
+    let global = score.global();
+    let outcome = match passed {
+        true => format!("{PASSED_ICON} Passed"),
+        false => format!("{FAILED_ICON} Failed"),
+    };
+    let _ = writeln!(md, "## CLOMonitor report\n");
+    let _ = writeln!(
+        md,
+        "**Global score: {} ({})** · Pass score: {pass_score} · {outcome}\n",
+        global.round(),
+        rating(profile, global),
+    );
+
+    // Sections scores
+    let _ = writeln!(md, "| Section | Score | Rating |");
+    let _ = writeln!(md, "|---------|------:|:------:|");
+    for section in Section::ALL {
+        match score.section_score(section) {
+            Some(v) => {
+                let _ = writeln!(md, "| {section} | {} | {} |", v.round(), rating(profile, v));
+            }
+            None => {
+                let _ = writeln!(md, "| {section} | n/a | n/a |");
+            }
+        }
+    }
+    md.push('\n');
+
+    // Checks
+    let mut rows = String::new();
+    let (mut passed, mut failed, mut exempt) = (0, 0, 0);
+    for check in registry.iter() {
+        let r = match report.get(check.id()) {
+            Some(r) => r,
+            None => continue,
+        };
+        let (icon, info) = check_info(&r);
+        match icon {
+            PASSED_ICON => passed += 1,
+            FAILED_ICON => failed += 1,
+            EXEMPT_ICON => exempt += 1,
+            _ => {}
+        }
+        let _ = writeln!(
+            rows,
+            "| {icon} | {} / {} | {} |",
+            check.section(),
+            check.name(),
+            escape(&info)
+        );
+    }
+    let _ = writeln!(
+        md,
+        "<details>\n<summary>Checks ({passed} passed, {failed} failed, {exempt} exempt)</summary>\n"
+    );
+    let _ = writeln!(md, "| | Check | Details |");
+    let _ = writeln!(md, "|-|-------|---------|");
+    md.push_str(&rows);
+    let _ = writeln!(md, "\n</details>");
+
+    // Changes since baseline
+    if let Some(changes) = changes {
+        let _ = writeln!(md, "\n### Changes since baseline\n");
+        let _ = writeln!(
+            md,
+            "Global score: {} → {} ({:+})\n",
+            changes.previous_score.round(),
+            global.round(),
+            changes.score_delta.round()
+        );
+        let check_name = |check_id: &String| match registry.get(check_id) {
+            Some(check) => format!("{} / {}", check.section(), check.name()),
+            None => check_id.clone(),
+        };
+        for check_id in &changes.newly_failing {
+            let _ = writeln!(
+                md,
+                "- {FAILED_ICON} Newly failing: {}",
+                check_name(check_id)
+            );
+        }
+        for check_id in &changes.newly_passing {
+            let _ = writeln!(
+                md,
+                "- {PASSED_ICON} Newly passing: {}",
+                check_name(check_id)
+            );
+        }
+        if changes.newly_failing.is_empty() && changes.newly_passing.is_empty() {
+            let _ = writeln!(md, "No checks changed.");
+        }
+    }
+
+    md
+}
+
+/// Return the icon and the information to display for the check result
+/// provided: a link to the evidence for the checks that passed, the exemption
+/// reason for exempt ones and the remediation hint for the ones that failed.
+fn check_info(r: &CheckOutput) -> (&'static str, String) {
+    if r.not_evaluated {
+        return (NOT_EVALUATED_ICON, "Not evaluated".to_string());
+    }
+    if let Some(err) = &r.error {
+        return (ERROR_ICON, format!("Error: {err}"));
+    }
+    if r.passed {
+        let url = r.evidence.as_ref().map(|e| &e.url).or(r.url.as_ref());
+        let info = match url {
+            Some(url) => format!("[evidence]({url})"),
+            None => String::new(),
+        };
+        return (PASSED_ICON, info);
+    }
+    if r.exempt {
+        let reason = r.exemption_reason.as_deref().unwrap_or_default();
+        return (EXEMPT_ICON, format!("Exempt: {reason}"));
+    }
+    let mut info = vec![];
+    if let Some(msg) = &r.failure_message {
+        info.push(msg.clone());
+    }
+    if let Some(details) = &r.details {
+        if let Some(remediation) = &details.remediation {
+            info.push(remediation.clone());
+        }
+        if let Some(docs_url) = &details.docs_url {
+            info.push(format!("[how to fix]({docs_url})"));
+        }
+    }
+    (FAILED_ICON, info.join(" · "))
+}
+
+/// Return the rating letter corresponding to the score provided.
+fn rating(profile: &Profile, score: f64) -> char {
+    profile.rating(score).to_ascii_uppercase()
+}
+
+/// Escape the text provided so that it can be used in a table cell.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clomonitor_core::score;
+    use serde_json::json;
+
+    #[test]
+    fn render_summary_with_changes() {
+        let mut report = Report::default();
+        report.documentation.readme = Some(
+            serde_json::from_value(json!({
+                "passed": true,
+                "exempt": false,
+                "url": "https://github.com/org/repo/blob/main/README.md",
+            }))
+            .unwrap(),
+        );
+        report.documentation.adopters = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": false,
+                "details": {"docs_url": "https://docs/adopters"},
+            }))
+            .unwrap(),
+        );
+        let registry = Registry::default();
+        let profile = Profile::default();
+        let score = score::calculate(&report, &registry, &profile);
+        let changes = Changes {
+            newly_failing: vec!["adopters".to_string()],
+            newly_passing: vec![],
+            previous_score: 100.0,
+            score_delta: score.global() - 100.0,
+        };
+
+        let md = render(
+            &report,
+            &score,
+            &registry,
+            &profile,
+            80.0,
+            false,
+            Some(&changes),
+        );
+        assert!(md.contains("**Global score: 91 (A)** · Pass score: 80 · ❌ Failed"));
+        assert!(md.contains("| Documentation | 91 | A |"));
+        assert!(md.contains("| License | n/a | n/a |"));
+        assert!(md.contains("<summary>Checks (1 passed, 1 failed, 0 exempt)</summary>"));
+        assert!(md.contains(
+            "| ✅ | Documentation / Readme | [evidence](https://github.com/org/repo/blob/main/README.md) |"
+        ));
+        assert!(
+            md.contains("| ❌ | Documentation / Adopters | [how to fix](https://docs/adopters) |")
+        );
+        assert!(md.contains("Global score: 100 → 91 (-9)"));
+        assert!(md.contains("- ❌ Newly failing: Documentation / Adopters"));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{baseline::Changes, policy::Violation};
+use anyhow::Error;
+use clap::ArgEnum;
+use clomonitor_core::{
+    linter::Report,
+    score::{Explanation, Score},
+};
+use serde::Serialize;
+
+/// Version of the output document schema. It must be increased every time a
+/// change that breaks the compatibility with previous versions is made.
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+/// Format used to print the linter results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub(crate) enum Format {
+    Table,
+    Json,
+    Yaml,
+    Sarif,
+    Junit,
+    Markdown,
+    Html,
+}
+
+/// Document holding the linter results, used by the machine readable formats.
+#[derive(Debug, Serialize)]
+pub(crate) struct Output<'a> {
+    pub schema_version: u32,
+    pub report: &'a Report,
+    pub score: &'a Score,
+    pub rating: char,
+    pub pass_score: f64,
+    pub passed: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<&'a Explanation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<&'a Changes>,
+
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub violations: &'a [Violation],
+}
+
+/// Render the output document provided in the format given.
+pub(crate) fn render<T: Serialize>(output: &T, format: Format) -> Result<String, Error> {
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(output)?,
+        Format::Yaml => serde_yaml::to_string(output)?,
+        _ => unreachable!("format not rendered from the output document"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clomonitor_core::{
+        linter::Registry,
+        score::{self, Profile},
+    };
+    use serde_json::Value;
+
+    #[test]
+    fn render_json_document() {
+        let report = Report::default();
+        let score = score::calculate(&report, &Registry::default(), &Profile::default());
+        let output = Output {
+            schema_version: SCHEMA_VERSION,
+            report: &report,
+            score: &score,
+            rating: 'd',
+            pass_score: 80.0,
+            passed: false,
+            explanation: None,
+            baseline: None,
+            violations: &[],
+        };
+        let doc: Value = serde_json::from_str(&render(&output, Format::Json).unwrap()).unwrap();
+        assert_eq!(doc["schema_version"], 1);
+        assert_eq!(doc["rating"], "d");
+        assert_eq!(doc["pass_score"], 80.0);
+        assert_eq!(doc["passed"], false);
+        assert!(doc["report"]["documentation"].is_object());
+        assert!(doc["score"]["global"].is_number());
+        assert!(doc.get("explanation").is_none());
+        assert!(doc.get("baseline").is_none());
+        assert!(doc.get("violations").is_none());
+    }
+}
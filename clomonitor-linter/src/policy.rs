@@ -0,0 +1,244 @@
+use anyhow::{format_err, Error};
+use clomonitor_core::{
+    linter::{Registry, Report, Section},
+    score::Score,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+/// Default linter pass score.
+pub(crate) const DEFAULT_PASS_SCORE: f64 = 80.0;
+
+/// Policy the linter results must comply with for the linter to succeed.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Policy {
+    pub pass_score: Option<f64>,
+
+    #[serde(default)]
+    pub min_scores: HashMap<Section, f64>,
+
+    #[serde(default)]
+    pub require: Vec<String>,
+}
+
+/// Rule of the policy that the linter results violated.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub(crate) enum Violation {
+    PassScore {
+        expected: f64,
+        actual: f64,
+    },
+    MinSectionScore {
+        section: Section,
+        expected: f64,
+        actual: Option<f64>,
+    },
+    RequiredCheck {
+        check_id: String,
+        status: CheckStatus,
+    },
+}
+
+/// Status of a required check that did not pass.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CheckStatus {
+    Failed,
+    NotRun,
+    NotEvaluated,
+    Error,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::PassScore { expected, actual } => write!(
+                f,
+                "global score {} is lower than the pass score {expected}",
+                actual.round()
+            ),
+            Violation::MinSectionScore {
+                section,
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "{section} score {} is lower than the minimum required {expected}",
+                actual.round()
+            ),
+            Violation::MinSectionScore {
+                section,
+                expected,
+                actual: None,
+            } => write!(
+                f,
+                "{section} section was not scored (minimum required is {expected})"
+            ),
+            Violation::RequiredCheck { check_id, status } => {
+                let status = match status {
+                    CheckStatus::Failed => "failed",
+                    CheckStatus::NotRun => "was not run",
+                    CheckStatus::NotEvaluated => "was not evaluated",
+                    CheckStatus::Error => "failed with an error",
+                };
+                write!(f, "required check {check_id} {status}")
+            }
+        }
+    }
+}
+
+impl Policy {
+    /// Load the policy from the YAML file located at the path provided.
+    pub(crate) fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format_err!("error reading policy {}: {err}", path.display()))?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    /// Return the pass score of the policy.
+    pub(crate) fn pass_score(&self) -> f64 {
+        self.pass_score.unwrap_or(DEFAULT_PASS_SCORE)
+    }
+
+    /// Check that all the required checks have been registered.
+    pub(crate) fn validate(&self, registry: &Registry) -> Result<(), Error> {
+        for check_id in &self.require {
+            if registry.get(check_id).is_none() {
+                return Err(format_err!("required check {check_id} not found"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate the linter results provided, returning the rules violated. The
+    /// pass score is only checked when requested.
+    pub(crate) fn evaluate(
+        &self,
+        report: &Report,
+        score: &Score,
+        check_pass_score: bool,
+    ) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        // Global pass score
+        if check_pass_score && score.global() < self.pass_score() {
+            violations.push(Violation::PassScore {
+                expected: self.pass_score(),
+                actual: score.global(),
+            });
+        }
+
+        // Sections minimum scores
+        for section in Section::ALL {
+            if let Some(expected) = self.min_scores.get(&section) {
+                let actual = score.section_score(section);
+                if actual.map_or(true, |actual| actual < *expected) {
+                    violations.push(Violation::MinSectionScore {
+                        section,
+                        expected: *expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        // Required checks
+        for check_id in &self.require {
+            let status = match report.get(check_id) {
+                None => Some(CheckStatus::NotRun),
+                Some(r) if r.not_evaluated => Some(CheckStatus::NotEvaluated),
+                Some(r) if r.error.is_some() => Some(CheckStatus::Error),
+                Some(r) if !r.passed && !r.exempt => Some(CheckStatus::Failed),
+                Some(_) => None,
+            };
+            if let Some(status) = status {
+                violations.push(Violation::RequiredCheck {
+                    check_id: check_id.clone(),
+                    status,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clomonitor_core::score::{self, Profile};
+
+    #[test]
+    fn policy_from_yaml() {
+        let policy: Policy = serde_yaml::from_str(
+            r#"
+            passScore: 70
+            minScores:
+              security: 60
+            require: [security_policy]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(policy.pass_score(), 70.0);
+        assert_eq!(policy.min_scores.get(&Section::Security), Some(&60.0));
+        assert_eq!(policy.require, vec!["security_policy"]);
+        assert!(policy.validate(&Registry::default()).is_ok());
+    }
+
+    #[test]
+    fn validate_unknown_required_check_fails() {
+        let policy = Policy {
+            require: vec!["unknown".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.validate(&Registry::default()).is_err());
+    }
+
+    #[test]
+    fn evaluate_reports_every_violated_rule() {
+        let mut report = Report::default();
+        report.documentation.readme = Some(true.into());
+        report.security.sbom = Some(true.into());
+        report.security.security_policy = Some(false.into());
+        let score = score::calculate(&report, &Registry::default(), &Profile::default());
+        let policy = Policy {
+            pass_score: Some(90.0),
+            min_scores: [(Section::Security, 70.0), (Section::Legal, 10.0)]
+                .into_iter()
+                .collect(),
+            require: vec![
+                "readme".to_string(),
+                "security_policy".to_string(),
+                "adopters".to_string(),
+            ],
+        };
+
+        let violations = policy.evaluate(&report, &score, true);
+        assert_eq!(violations.len(), 5);
+        assert!(matches!(violations[0], Violation::PassScore { .. }));
+        assert_eq!(
+            violations[2],
+            Violation::MinSectionScore {
+                section: Section::Legal,
+                expected: 10.0,
+                actual: None
+            }
+        );
+        assert_eq!(
+            violations[3].to_string(),
+            "required check security_policy failed"
+        );
+        assert_eq!(
+            violations[4],
+            Violation::RequiredCheck {
+                check_id: "adopters".to_string(),
+                status: CheckStatus::NotRun
+            }
+        );
+
+        // The pass score is not checked when not requested
+        assert_eq!(policy.evaluate(&report, &score, false).len(), 4);
+    }
+}
@@ -0,0 +1,350 @@
+use anyhow::{format_err, Error};
+use clomonitor_core::{
+    git::{self, Credentials, RemoteOptions},
+    linter::{lint, CheckSet, LintOptions, Registry, Report, METADATA_FILE},
+    score::{self, Explanation, Profile, Score},
+};
+use futures::future;
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use tempdir::TempDir;
+
+/// Project manifest, listing the repositories that belong to the project.
+#[derive(Debug, PartialEq, Deserialize)]
+pub(crate) struct Manifest {
+    pub name: Option<String>,
+    pub repositories: Vec<ManifestRepository>,
+}
+
+/// Repository entry in the project manifest. When no path is provided, the
+/// repository is cloned from its url.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ManifestRepository {
+    pub name: Option<String>,
+    pub url: String,
+    pub path: Option<PathBuf>,
+
+    #[serde(rename = "ref")]
+    pub reference: Option<String>,
+
+    #[serde(default = "default_check_sets")]
+    pub check_sets: Vec<CheckSet>,
+}
+
+/// Check sets used when a repository in the manifest doesn't specify any.
+fn default_check_sets() -> Vec<CheckSet> {
+    vec![CheckSet::Code, CheckSet::Community]
+}
+
+impl Manifest {
+    /// Load the manifest from the YAML file located at the path provided.
+    /// Repositories relative paths are resolved from the manifest location.
+    pub(crate) fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format_err!("error reading manifest {}: {err}", path.display()))?;
+        let mut manifest: Manifest = serde_yaml::from_str(&content)?;
+        if manifest.repositories.is_empty() {
+            return Err(format_err!("manifest does not contain any repositories"));
+        }
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        for repository in &mut manifest.repositories {
+            if let Some(path) = &repository.path {
+                repository.path = Some(base.join(path));
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+impl ManifestRepository {
+    /// Return the repository name, using the last segment of its url when
+    /// not provided.
+    pub(crate) fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self
+                .url
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+}
+
+/// Options used to lint the project's repositories.
+pub(crate) struct Options<'a> {
+    pub checks_file: Option<&'a Path>,
+    pub profile: &'a Profile,
+    pub offline: bool,
+}
+
+/// Results of linting one of the project's repositories.
+pub(crate) struct RepositoryResults {
+    pub name: String,
+    pub url: String,
+    pub results: Result<LintResults, Error>,
+}
+
+/// Linter report of a repository, along with its score and the registry
+/// used to produce them.
+pub(crate) struct LintResults {
+    pub registry: Registry,
+    pub report: Report,
+    pub score: Score,
+}
+
+/// Lint all the repositories in the manifest concurrently.
+pub(crate) async fn lint_all(manifest: &Manifest, options: &Options<'_>) -> Vec<RepositoryResults> {
+    future::join_all(manifest.repositories.iter().map(|repository| async move {
+        RepositoryResults {
+            name: repository.name(),
+            url: repository.url.clone(),
+            results: lint_repository(repository, options).await,
+        }
+    }))
+    .await
+}
+
+/// Prepare the options used to clone the repository at the url provided,
+/// using the GITHUB_TOKEN or GITLAB_TOKEN environment variables (the same ones
+/// used when linting) as credentials.
+pub(crate) fn clone_options(url: &str) -> RemoteOptions {
+    RemoteOptions {
+        credentials: Credentials::from_token(
+            url,
+            env::var("GITHUB_TOKEN").ok().as_deref(),
+            env::var("GITLAB_TOKEN").ok().as_deref(),
+        ),
+        ..Default::default()
+    }
+}
+
+/// Lint the repository provided, cloning it first if needed.
+async fn lint_repository(
+    repository: &ManifestRepository,
+    options: &Options<'_>,
+) -> Result<LintResults, Error> {
+    let tmp_dir;
+    let root = match &repository.path {
+        Some(path) => path.clone(),
+        None => {
+            tmp_dir = TempDir::new("clomonitor")?;
+            git::clone(
+                &repository.url,
+                tmp_dir.path(),
+                repository.reference.as_deref(),
+                &clone_options(&repository.url),
+            )
+            .await
+            .map_err(|err| format_err!("error cloning repository: {err}"))?;
+            tmp_dir.path().to_path_buf()
+        }
+    };
+
+    let mut registry = Registry::default();
+    if let Some(checks_file) = options.checks_file {
+        registry.register_custom_checks(checks_file)?;
+    }
+    registry.register_custom_checks(root.join(METADATA_FILE))?;
+    let lint_options = LintOptions {
+        check_sets: repository.check_sets.clone(),
+        root,
+        url: repository.url.clone(),
+        offline: options.offline,
+        github_token: env::var("GITHUB_TOKEN").ok(),
+        gitlab_token: env::var("GITLAB_TOKEN").ok(),
+    };
+    let report = lint(lint_options, &registry).await?;
+    let score = score::calculate(&report, &registry, options.profile);
+    Ok(LintResults {
+        registry,
+        report,
+        score,
+    })
+}
+
+/// Merge the scores of the repositories linted successfully into the project
+/// score, the same way the tracker does.
+pub(crate) fn project_score(repositories: &[RepositoryResults]) -> Option<Score> {
+    let scores: Vec<Score> = repositories
+        .iter()
+        .filter_map(|r| r.results.as_ref().ok())
+        .map(|r| r.score.clone())
+        .collect();
+    if scores.is_empty() {
+        return None;
+    }
+    Some(score::merge(scores))
+}
+
+/// Merge the score explanations of the repositories linted successfully.
+pub(crate) fn project_explanation(
+    repositories: &[RepositoryResults],
+    profile: &Profile,
+) -> Explanation {
+    score::merge_explanations(
+        repositories
+            .iter()
+            .filter_map(|r| {
+                let results = r.results.as_ref().ok()?;
+                let explanation = score::explain(&results.report, &results.registry, profile);
+                Some((r.name.clone(), explanation))
+            })
+            .collect(),
+    )
+}
+
+/// Document holding the project linter results, used by the machine readable
+/// formats.
+#[derive(Debug, Serialize)]
+pub(crate) struct Output<'a> {
+    pub schema_version: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+
+    pub repositories: Vec<RepositoryOutput<'a>>,
+    pub score: Option<&'a Score>,
+    pub rating: Option<char>,
+    pub pass_score: f64,
+    pub passed: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<&'a Explanation>,
+}
+
+/// Linter results of a repository in the project output document.
+#[derive(Debug, Serialize)]
+pub(crate) struct RepositoryOutput<'a> {
+    pub name: &'a str,
+    pub url: &'a str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<&'a Report>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<&'a Score>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<char>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl<'a> RepositoryOutput<'a> {
+    /// Build the output of the repository results provided.
+    pub(crate) fn new(r: &'a RepositoryResults, profile: &Profile) -> Self {
+        match &r.results {
+            Ok(results) => Self {
+                name: &r.name,
+                url: &r.url,
+                report: Some(&results.report),
+                score: Some(&results.score),
+                rating: Some(profile.rating(results.score.global())),
+                error: None,
+            },
+            Err(err) => Self {
+                name: &r.name,
+                url: &r.url,
+                report: None,
+                score: None,
+                rating: None,
+                error: Some(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_from_file() {
+        let tmp_dir = TempDir::new("clomonitor").unwrap();
+        let dir = tmp_dir.path();
+        let path = dir.join("project.yml");
+        fs::write(
+            &path,
+            r#"
+name: project
+repositories:
+  - url: https://github.com/org/repo1/
+    path: repo1
+    checkSets: [code-lite]
+  - name: docs
+    url: https://github.com/org/repo2
+    ref: v1.0.0
+"#,
+        )
+        .unwrap();
+
+        let manifest = Manifest::from_file(&path).unwrap();
+        assert_eq!(manifest.name.as_deref(), Some("project"));
+        let (r1, r2) = (&manifest.repositories[0], &manifest.repositories[1]);
+        assert_eq!(r1.name(), "repo1");
+        assert_eq!(r1.path, Some(dir.join("repo1")));
+        assert_eq!(r1.check_sets, vec![CheckSet::CodeLite]);
+        assert_eq!(r2.name(), "docs");
+        assert_eq!(r2.path, None);
+        assert_eq!(r2.reference.as_deref(), Some("v1.0.0"));
+        assert_eq!(r2.check_sets, default_check_sets());
+
+        fs::write(&path, "repositories: []").unwrap();
+        assert!(Manifest::from_file(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn lint_all_and_merge_scores() {
+        let tmp_dir = TempDir::new("clomonitor").unwrap();
+        let dir = tmp_dir.path();
+        fs::create_dir_all(dir.join("repo1")).unwrap();
+        fs::create_dir_all(dir.join("repo2")).unwrap();
+        fs::write(dir.join("repo1").join("README.md"), "# repo1").unwrap();
+        let manifest = Manifest {
+            name: None,
+            repositories: vec![
+                ManifestRepository {
+                    name: None,
+                    url: "https://github.com/org/repo1".to_string(),
+                    path: Some(dir.join("repo1")),
+                    reference: None,
+                    check_sets: vec![CheckSet::Docs],
+                },
+                ManifestRepository {
+                    name: None,
+                    url: "https://github.com/org/repo2".to_string(),
+                    path: Some(dir.join("repo2")),
+                    reference: None,
+                    check_sets: vec![CheckSet::Docs],
+                },
+            ],
+        };
+        let profile = Profile::default();
+        let options = Options {
+            checks_file: None,
+            profile: &profile,
+            offline: true,
+        };
+
+        let repositories = lint_all(&manifest, &options).await;
+        assert_eq!(repositories.len(), 2);
+        let scores: Vec<Score> = repositories
+            .iter()
+            .map(|r| r.results.as_ref().unwrap().score.clone())
+            .collect();
+        assert!(scores[0].global() > scores[1].global());
+        assert_eq!(project_score(&repositories), Some(score::merge(scores)));
+        assert_eq!(
+            project_explanation(&repositories, &profile).global,
+            project_score(&repositories).unwrap().global()
+        );
+    }
+}
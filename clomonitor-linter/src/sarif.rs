@@ -0,0 +1,322 @@
+use anyhow::Error;
+use clomonitor_core::linter::{Check, CheckOutput, Registry, Report, Section};
+use serde::Serialize;
+use std::{fs, path::Path};
+
+/// SARIF schema url.
+const SCHEMA_URL: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF version.
+const VERSION: &str = "2.1.0";
+
+/// Linter information url.
+const INFORMATION_URL: &str = "https://github.com/cncf/clomonitor";
+
+/// SARIF log, the top level document.
+#[derive(Debug, Serialize)]
+struct Log {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+/// SARIF run, holding the results produced by the linter.
+#[derive(Debug, Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+/// SARIF tool.
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+/// SARIF tool driver.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+/// SARIF reporting descriptor, one per check.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    name: String,
+    short_description: Message,
+    full_description: Message,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<Message>,
+    properties: RuleProperties,
+}
+
+/// Additional properties of a rule.
+#[derive(Debug, Serialize)]
+struct RuleProperties {
+    section: String,
+    weight: usize,
+}
+
+/// SARIF result, one per failed or exempt check.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<Suppression>,
+}
+
+/// SARIF message.
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+/// SARIF location.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+/// SARIF physical location.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+/// SARIF artifact location.
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+/// SARIF region.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+}
+
+/// SARIF suppression, used for exempt checks.
+#[derive(Debug, Serialize)]
+struct Suppression {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justification: Option<String>,
+}
+
+/// Render the linter report provided as a SARIF 2.1.0 log. Each check
+/// registered becomes a rule, and each failed or exempt check a result
+/// (exempt checks are reported as suppressed).
+pub(crate) fn render(report: &Report, registry: &Registry, root: &Path) -> Result<String, Error> {
+    let mut rules = vec![];
+    let mut results = vec![];
+    for (rule_index, check) in registry.iter().enumerate() {
+        rules.push(rule(check));
+        if let Some(r) = report.get(check.id()) {
+            if let Some(result) = result(check, rule_index, &r, root) {
+                results.push(result);
+            }
+        }
+    }
+
+    let log = Log {
+        schema: SCHEMA_URL,
+        version: VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: INFORMATION_URL,
+                    rules,
+                },
+            },
+            results,
+        }],
+    };
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+/// Build a SARIF rule for the check provided.
+fn rule(check: &dyn Check) -> Rule {
+    let description = format!("{} / {}", check.section(), check.name());
+    Rule {
+        id: check.id().to_string(),
+        name: check.name().to_string(),
+        short_description: Message {
+            text: description.clone(),
+        },
+        full_description: Message {
+            text: check.remediation().map_or(description, ToString::to_string),
+        },
+        help_uri: check.docs_url(),
+        help: check.remediation().map(|text| Message {
+            text: text.to_string(),
+        }),
+        properties: RuleProperties {
+            section: check.section().to_string(),
+            weight: check.weight(),
+        },
+    }
+}
+
+/// Build a SARIF result for the check result provided, if it failed or is
+/// exempt. Results not evaluated or that failed with an error are skipped.
+fn result(
+    check: &dyn Check,
+    rule_index: usize,
+    r: &CheckOutput,
+    root: &Path,
+) -> Option<SarifResult> {
+    if r.passed || r.not_evaluated || r.error.is_some() {
+        return None;
+    }
+    let message = match &r.failure_message {
+        Some(msg) => msg.clone(),
+        None => format!("{} check failed", check.name()),
+    };
+    let suppressions = match r.exempt {
+        true => vec![Suppression {
+            kind: "external",
+            justification: r.exemption_reason.clone(),
+        }],
+        false => vec![],
+    };
+    Some(SarifResult {
+        rule_id: check.id().to_string(),
+        rule_index,
+        level: "error",
+        message: Message { text: message },
+        locations: location(check, r, root).into_iter().collect(),
+        suppressions,
+    })
+}
+
+/// Return the location of the file relevant to the check result provided,
+/// when known: the file where the evidence was found, the license file for
+/// the license checks or the README file otherwise.
+fn location(check: &dyn Check, r: &CheckOutput, root: &Path) -> Option<Location> {
+    let (uri, start_line) = if let Some(evidence) = &r.evidence {
+        (evidence.file.clone(), Some(evidence.line))
+    } else if check.section() == Section::License {
+        (find_root_file(root, &["license", "copying"])?, None)
+    } else {
+        (find_root_file(root, &["readme"])?, None)
+    };
+    Some(Location {
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation { uri },
+            region: start_line.map(|start_line| Region { start_line }),
+        },
+    })
+}
+
+/// Find a file in the root path provided whose name starts with any of the
+/// prefixes given (case insensitive), returning its name.
+fn find_root_file(root: &Path, prefixes: &[&str]) -> Option<String> {
+    let mut names: Vec<String> = fs::read_dir(root)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            if !entry.file_type().ok()?.is_file() {
+                return None;
+            }
+            entry.file_name().into_string().ok()
+        })
+        .filter(|name| {
+            let name = name.to_lowercase();
+            prefixes.iter().any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+    names.sort();
+    names.into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn render_failed_and_exempt_checks() {
+        let mut report = Report::default();
+        report.documentation.adopters = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": false,
+                "failure_message": "adopters not found",
+            }))
+            .unwrap(),
+        );
+        report.documentation.changelog = Some(
+            serde_json::from_value(json!({
+                "passed": true,
+                "exempt": false,
+            }))
+            .unwrap(),
+        );
+        report.documentation.readme = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": false,
+                "evidence": {"file": "docs/README.md", "line": 3, "snippet": "", "url": ""},
+            }))
+            .unwrap(),
+        );
+        report.security.sbom = Some(
+            serde_json::from_value(json!({
+                "passed": false,
+                "exempt": true,
+                "exemption_reason": "not applicable",
+            }))
+            .unwrap(),
+        );
+
+        let registry = Registry::default();
+        let log: Value =
+            serde_json::from_str(&render(&report, &registry, Path::new("/nonexistent")).unwrap())
+                .unwrap();
+        assert_eq!(log["version"], VERSION);
+        let run = &log["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"].as_array().unwrap().len(),
+            registry.iter().count()
+        );
+
+        let results = run["results"].as_array().unwrap();
+        let rule_ids: Vec<&str> = results
+            .iter()
+            .map(|r| r["ruleId"].as_str().unwrap())
+            .collect();
+        assert_eq!(rule_ids, vec!["adopters", "readme", "sbom"]);
+        assert_eq!(results[0]["message"]["text"], "adopters not found");
+        assert!(results[0].get("locations").is_none());
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"],
+            json!({"artifactLocation": {"uri": "docs/README.md"}, "region": {"startLine": 3}})
+        );
+        assert_eq!(
+            results[2]["suppressions"],
+            json!([{"kind": "external", "justification": "not applicable"}])
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use anyhow::Error;
+use deadpool_postgres::{Client as DbClient, Pool, Timeouts};
+use rand::Rng;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Job used to track a repository. Each repository has a single job, which is
+/// claimed by one of the tracker workers when it's due. Claims are leases that
+/// have to be renewed periodically (heartbeats) while the repository is being
+/// tracked, so that they are released if the worker holding them dies.
+#[derive(Debug, Clone)]
+pub(crate) struct Job {
+    job_id: Uuid,
+    repository_id: Uuid,
+    attempts: i32,
+    worker_id: String,
+}
+
+impl Job {
+    /// Get the id of the repository to track.
+    pub(crate) fn repository_id(&self) -> Uuid {
+        self.repository_id
+    }
+
+    /// Get the number of times the job has been claimed since it last
+    /// succeeded.
+    pub(crate) fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    /// Renew the job's lease every third of its duration until the task
+    /// running it is aborted.
+    pub(crate) async fn heartbeat(self, db_pool: Pool, lease: Duration) {
+        loop {
+            sleep(lease / 3).await;
+            if let Err(err) = self.renew_lease(&db_pool, lease).await {
+                warn!("error renewing lease [job id: {}]: {err}", self.job_id);
+            }
+        }
+    }
+
+    /// Renew the job's lease. We don't wait for a database client longer than
+    /// the time between heartbeats, so that the next one can try again before
+    /// the lease expires.
+    async fn renew_lease(&self, db_pool: &Pool, lease: Duration) -> Result<(), Error> {
+        let timeouts = Timeouts {
+            wait: Some(lease / 3),
+            create: Some(lease / 3),
+            recycle: Some(lease / 3),
+        };
+        let db = db_pool.timeout_get(&timeouts).await?;
+        let renewed = db
+            .execute(
+                "
+                update tracking_job set
+                    locked_until = current_timestamp + make_interval(secs => $1::integer),
+                    heartbeat_at = current_timestamp
+                where tracking_job_id = $2::uuid
+                and locked_by = $3::text;
+                ",
+                &[&secs(lease), &self.job_id, &self.worker_id],
+            )
+            .await?;
+        if renewed == 0 {
+            warn!("lease lost [job id: {}]", self.job_id);
+        }
+        Ok(())
+    }
+
+    /// Release the job after tracking the repository successfully, scheduling
+    /// its next run after the delay provided.
+    pub(crate) async fn complete(&self, db: &DbClient, next_run: Duration) -> Result<(), Error> {
+        db.execute(
+            "
+            update tracking_job set
+                run_at = current_timestamp + make_interval(secs => $1::integer),
+                attempts = 0,
+                locked_by = null,
+                locked_until = null,
+                last_error = null
+            where tracking_job_id = $2::uuid
+            and locked_by = $3::text;
+            ",
+            &[&secs(next_run), &self.job_id, &self.worker_id],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Release the job after failing to track the repository, scheduling its
+    /// next run after the delay provided. When the job is not retried again,
+    /// its attempts are reset.
+    pub(crate) async fn fail(
+        &self,
+        db: &DbClient,
+        err: &str,
+        next_run: Duration,
+        retry: bool,
+    ) -> Result<(), Error> {
+        db.execute(
+            "
+            update tracking_job set
+                run_at = current_timestamp + make_interval(secs => $1::integer),
+                attempts = case when $2::boolean then attempts else 0 end,
+                locked_by = null,
+                locked_until = null,
+                last_error = $3::text
+            where tracking_job_id = $4::uuid
+            and locked_by = $5::text;
+            ",
+            &[&secs(next_run), &retry, &err, &self.job_id, &self.worker_id],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Create a job for each of the repositories that don't have one yet, due
+/// right away. When all is true, the jobs of all the repositories not being
+/// tracked at the moment are made due right away as well. Returns the number
+/// of jobs created or updated.
+pub(crate) async fn enqueue(db: &DbClient, all: bool) -> Result<u64, Error> {
+    let query = if all {
+        "
+        insert into tracking_job (repository_id)
+        select repository_id from repository
+        on conflict (repository_id) do update
+        set run_at = least(tracking_job.run_at, current_timestamp);
+        "
+    } else {
+        "
+        insert into tracking_job (repository_id)
+        select repository_id from repository
+        on conflict (repository_id) do nothing;
+        "
+    };
+    Ok(db.execute(query, &[]).await?)
+}
+
+/// Claim the next job due, if any, for the worker provided. Jobs claimed by
+/// other workers are skipped unless their lease has expired.
+pub(crate) async fn claim(
+    db: &DbClient,
+    worker_id: &str,
+    lease: Duration,
+) -> Result<Option<Job>, Error> {
+    let row = db
+        .query_opt(
+            "
+            update tracking_job set
+                attempts = attempts + 1,
+                locked_by = $1::text,
+                locked_until = current_timestamp + make_interval(secs => $2::integer),
+                heartbeat_at = current_timestamp
+            where tracking_job_id = (
+                select tracking_job_id from tracking_job
+                where run_at <= current_timestamp
+                and (locked_until is null or locked_until < current_timestamp)
+                order by run_at asc
+                limit 1
+                for update skip locked
+            )
+            returning tracking_job_id, repository_id, attempts;
+            ",
+            &[&worker_id, &secs(lease)],
+        )
+        .await?;
+    Ok(row.map(|row| Job {
+        job_id: row.get("tracking_job_id"),
+        repository_id: row.get("repository_id"),
+        attempts: row.get("attempts"),
+        worker_id: worker_id.to_string(),
+    }))
+}
+
+/// Return the delay until the next run of a job that succeeded: the interval
+/// provided plus a random jitter, so that runs are spread over time.
+pub(crate) fn next_run_delay(interval: Duration, jitter: Duration) -> Duration {
+    let jitter = match jitter.as_secs() {
+        0 => 0,
+        max => rand::thread_rng().gen_range(0..=max),
+    };
+    interval + Duration::from_secs(jitter)
+}
+
+/// Return the delay until a job that failed the number of attempts provided
+/// is retried. The base delay is doubled on each attempt.
+pub(crate) fn retry_delay(base: Duration, attempts: i32) -> Duration {
+    let exp = attempts.clamp(1, 16) as u32 - 1;
+    base * 2_u32.pow(exp)
+}
+
+/// Return the number of seconds of the duration provided, as expected by the
+/// queries above.
+fn secs(duration: Duration) -> i32 {
+    duration.as_secs().min(i32::MAX as u64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_run_delay_adds_jitter() {
+        let interval = Duration::from_secs(3600);
+        assert_eq!(next_run_delay(interval, Duration::ZERO), interval);
+        let delay = next_run_delay(interval, Duration::from_secs(600));
+        assert!(delay >= interval && delay <= interval + Duration::from_secs(600));
+    }
+
+    #[test]
+    fn retry_delay_backs_off_exponentially() {
+        let base = Duration::from_secs(60);
+        assert_eq!(retry_delay(base, 0), base);
+        assert_eq!(retry_delay(base, 1), base);
+        assert_eq!(retry_delay(base, 2), base * 2);
+        assert_eq!(retry_delay(base, 3), base * 4);
+        assert_eq!(retry_delay(base, 100), base * 32768);
+    }
+
+    #[test]
+    fn secs_saturates() {
+        assert_eq!(secs(Duration::from_secs(90)), 90);
+        assert_eq!(secs(Duration::from_secs(u64::MAX)), i32::MAX);
+    }
+}
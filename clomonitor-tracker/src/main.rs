@@ -1,12 +1,13 @@
-use anyhow::{format_err, Error};
+use anyhow::Error;
 use clap::Parser;
 use config::{Config, File};
 use deadpool_postgres::{Config as DbConfig, Runtime};
 use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
 use postgres_openssl::MakeTlsConnector;
-use std::path::PathBuf;
-use which::which;
+use std::{env, path::PathBuf};
 
+mod job;
+mod mirror;
 mod repository;
 mod tracker;
 
@@ -16,6 +17,10 @@ struct Args {
     /// Config file path
     #[clap(short, long, parse(from_os_str))]
     config: PathBuf,
+
+    /// Run as a daemon, tracking repositories periodically
+    #[clap(short, long)]
+    daemon: bool,
 }
 
 #[tokio::main]
@@ -23,20 +28,31 @@ async fn main() -> Result<(), Error> {
     let args = Args::parse();
 
     // Setup logging
-    if std::env::var_os("RUST_LOG").is_none() {
-        std::env::set_var("RUST_LOG", "clomonitor_tracker=debug")
+    if env::var_os("RUST_LOG").is_none() {
+        env::set_var("RUST_LOG", "clomonitor_tracker=debug")
     }
     tracing_subscriber::fmt::init();
 
-    // Check if required external tools are available
-    if which("git").is_err() {
-        return Err(format_err!("git not found in PATH"));
-    }
-
     // Setup configuration
     let mut cfg = Config::new();
     cfg.set_default("db.dbname", "clomonitor")?;
     cfg.set_default("tracker.concurrency", 10)?;
+    cfg.set_default(
+        "tracker.cache.path",
+        env::temp_dir()
+            .join("clomonitor-tracker")
+            .to_string_lossy()
+            .to_string(),
+    )?;
+    cfg.set_default("tracker.cache.maxSizeMb", 10240)?;
+    cfg.set_default("tracker.cache.maxAgeDays", 7)?;
+    cfg.set_default("tracker.snapshots.retentionDays", 365)?;
+    cfg.set_default("tracker.maxStalenessHours", 24)?;
+    cfg.set_default("tracker.daemon.intervalMinutes", 60)?;
+    cfg.set_default("tracker.daemon.jitterMinutes", 10)?;
+    cfg.set_default("tracker.jobs.leaseSeconds", 60)?;
+    cfg.set_default("tracker.jobs.maxAttempts", 3)?;
+    cfg.set_default("tracker.jobs.retryDelaySeconds", 300)?;
     cfg.merge(File::from(args.config))?;
 
     // Setup database
@@ -47,7 +63,11 @@ async fn main() -> Result<(), Error> {
     let db_pool = db_cfg.create_pool(Some(Runtime::Tokio1), connector)?;
 
     // Run tracker
-    tracker::run(cfg, db_pool).await?;
+    if args.daemon {
+        tracker::run_daemon(cfg, db_pool).await?;
+    } else {
+        tracker::run(cfg, db_pool).await?;
+    }
 
     Ok(())
 }
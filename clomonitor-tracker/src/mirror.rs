@@ -0,0 +1,439 @@
+use anyhow::{format_err, Error};
+use clomonitor_core::git::{self, RemoteOptions};
+use fs2::FileExt;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+use tokio::{sync::Mutex as AsyncMutex, task};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// File in each mirror whose modification time records when it was last used.
+const LAST_USED_FILE: &str = "clomonitor-last-used";
+
+/// Cache of bare mirrors of the repositories tracked. Mirrors are updated
+/// incrementally (only the new objects are fetched) and each lint is run on
+/// its own worktree (sharing the mirror's objects), which is removed once it
+/// is no longer needed.
+///
+/// The cache can be shared by multiple trackers: operations on mirrors are
+/// serialized using lock files, and each tracker keeps its worktrees in its
+/// own directory.
+pub(crate) struct MirrorCache {
+    mirrors_dir: PathBuf,
+    locks_dir: PathBuf,
+    worktrees_dir: PathBuf,
+    max_size: u64,
+    max_age: Duration,
+    locks: Mutex<HashMap<PathBuf, Arc<AsyncMutex<()>>>>,
+    _worktrees_lock: File,
+}
+
+impl MirrorCache {
+    /// Create a new mirror cache in the path provided for the tracker with
+    /// the id given. The tracker's worktrees directory is locked while the
+    /// cache is alive, and the ones left behind by trackers no longer running
+    /// (i.e. if they were killed) are removed.
+    pub(crate) fn new(
+        path: &Path,
+        id: &str,
+        max_size: u64,
+        max_age: Duration,
+    ) -> Result<Self, Error> {
+        let mirrors_dir = path.join("mirrors");
+        let locks_dir = path.join("locks");
+        let worktrees_root = path.join("worktrees");
+        fs::create_dir_all(&mirrors_dir)?;
+        fs::create_dir_all(&locks_dir)?;
+        fs::create_dir_all(&worktrees_root)?;
+
+        // Setup this tracker's worktrees directory holding the cache lock, so
+        // that trackers starting at the same time don't remove each other's
+        let cache_lock = lock_file(&path.join("cache.lock"))?;
+        remove_stale_worktrees(&worktrees_root)?;
+        let worktrees_lock = try_lock_file(&worktrees_root.join(format!("{id}.lock")))?
+            .ok_or_else(|| format_err!("worktrees directory {id} already in use"))?;
+        let worktrees_dir = worktrees_root.join(id);
+        fs::create_dir_all(&worktrees_dir)?;
+        drop(cache_lock);
+
+        Ok(Self {
+            mirrors_dir,
+            locks_dir,
+            worktrees_dir,
+            max_size,
+            max_age,
+            locks: Mutex::new(HashMap::new()),
+            _worktrees_lock: worktrees_lock,
+        })
+    }
+
+    /// Update the mirror of the repository at the url provided (creating it
+    /// if needed) and check out its remote HEAD in a new worktree.
+    ///
+    /// The mirror is locked exclusively while it's being updated, and then
+    /// shared until the worktree is dropped, as the worktree uses the mirror's
+    /// objects. All this happens in a blocking task that holds the lock, so
+    /// the mirror stays locked while the fetch runs even if this future is
+    /// cancelled (i.e. when tracking the repository times out).
+    pub(crate) async fn checkout(
+        &self,
+        url: &str,
+        opts: &RemoteOptions,
+    ) -> Result<Worktree, Error> {
+        let name = mirror_name(url);
+        let mirror = self.mirrors_dir.join(&name);
+        let lock = self.lock(&mirror);
+        let _guard = lock.lock().await;
+
+        let (url, opts) = (url.to_string(), opts.clone());
+        let lock_path = self.mirror_lock_path(&name);
+        let worktree_path = self.worktrees_dir.join(Uuid::new_v4().to_string());
+        task::spawn_blocking(move || {
+            let mirror_lock = lock_file(&lock_path)?;
+            update_mirror(&url, &mirror, &opts)?;
+
+            // Check out remote HEAD in a new worktree
+            let worktree = Worktree {
+                path: worktree_path,
+                mirror_lock,
+            };
+            git::checkout_shared(&mirror, &worktree.path)?;
+
+            // Keep the mirror locked (shared) while the worktree is in use.
+            // Locks conversions aren't atomic, so make sure the mirror wasn't
+            // evicted in the meantime
+            FileExt::lock_shared(&worktree.mirror_lock)?;
+            if !mirror.exists() {
+                return Err(format_err!("mirror {} evicted", mirror.display()));
+            }
+            Ok(worktree)
+        })
+        .await?
+    }
+
+    /// Remove the mirrors that haven't been used in more than the maximum age.
+    /// Remaining mirrors are kept from the most to the least recently used
+    /// while they fit in the maximum size, and the rest are removed as well.
+    /// Mirrors locked (being updated or used by a worktree) are skipped.
+    pub(crate) fn evict(&self) -> Result<(), Error> {
+        let mut mirrors = vec![];
+        for entry in fs::read_dir(&self.mirrors_dir)? {
+            let path = entry?.path();
+            let last_used = fs::metadata(path.join(LAST_USED_FILE))
+                .and_then(|md| md.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            let size = dir_size(&path)?;
+            mirrors.push((path, last_used, size));
+        }
+        mirrors.sort_by_key(|(_, last_used, _)| Reverse(*last_used));
+
+        let now = SystemTime::now();
+        let mut cache_size = 0;
+        for (path, last_used, size) in mirrors {
+            let age = now.duration_since(last_used).unwrap_or_default();
+            if age <= self.max_age && cache_size + size <= self.max_size {
+                cache_size += size;
+                continue;
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            match try_lock_file(&self.mirror_lock_path(&name))? {
+                Some(_file_guard) => {
+                    debug!("evicting mirror {}", path.display());
+                    fs::remove_dir_all(&path)?;
+                }
+                None => debug!("mirror {} in use, not evicted", path.display()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the path of the lock file of the mirror with the name provided.
+    fn mirror_lock_path(&self, name: &str) -> PathBuf {
+        self.locks_dir.join(format!("{name}.lock"))
+    }
+
+    /// Return the lock used to serialize the operations on the mirror
+    /// located at the path provided.
+    fn lock(&self, mirror: &Path) -> Arc<AsyncMutex<()>> {
+        let mut locks = self.locks.lock().unwrap();
+        locks.entry(mirror.to_path_buf()).or_default().clone()
+    }
+}
+
+/// Worktree of a mirror. It is removed when dropped, so it is cleaned up even
+/// if tracking the repository fails or times out. The mirror is kept locked
+/// while the worktree is alive, so that it isn't evicted.
+pub(crate) struct Worktree {
+    path: PathBuf,
+    mirror_lock: File,
+}
+
+impl Worktree {
+    /// Get the worktree's path.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_dir_all(&self.path) {
+            if err.kind() != io::ErrorKind::NotFound {
+                warn!("error removing worktree {}: {err}", self.path.display());
+            }
+        }
+    }
+}
+
+/// Return the name of the mirror of the repository at the url provided.
+fn mirror_name(url: &str) -> String {
+    let url = url.split("://").last().unwrap_or(url);
+    let name: String = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' => c,
+            _ => '_',
+        })
+        .collect();
+    format!("{name}.git")
+}
+
+/// Update the mirror of the repository at the url provided, recreating it
+/// from scratch if it looks corrupted. The mirror must be locked exclusively.
+fn update_mirror(url: &str, mirror: &Path, opts: &RemoteOptions) -> Result<(), Error> {
+    let existed = mirror.exists();
+    let stats = match git::fetch_head(url, mirror, opts) {
+        Ok(stats) => stats,
+        Err(err @ git::Error::Git(_)) if existed => {
+            warn!(
+                "error updating mirror {}, recreating it: {err}",
+                mirror.display()
+            );
+            fs::remove_dir_all(mirror)?;
+            git::fetch_head(url, mirror, opts)?
+        }
+        Err(err) => {
+            if !existed && mirror.exists() {
+                fs::remove_dir_all(mirror)?;
+            }
+            return Err(err.into());
+        }
+    };
+    debug!(
+        "mirror {} updated ({} objects, {} bytes received)",
+        mirror.display(),
+        stats.received_objects,
+        stats.received_bytes
+    );
+    fs::write(mirror.join(LAST_USED_FILE), "")?;
+    Ok(())
+}
+
+/// Remove the worktrees directories (and their lock files) located at the
+/// path provided whose trackers are no longer running.
+fn remove_stale_worktrees(path: &Path) -> Result<(), Error> {
+    // Directories whose lock file isn't locked anymore
+    for entry in fs::read_dir(path)? {
+        let lock_path = entry?.path();
+        if lock_path.extension().unwrap_or_default() != "lock" {
+            continue;
+        }
+        if let Some(_file_guard) = try_lock_file(&lock_path)? {
+            let dir = lock_path.with_extension("");
+            if dir.exists() {
+                debug!("removing stale worktrees directory {}", dir.display());
+                fs::remove_dir_all(&dir)?;
+            }
+            fs::remove_file(&lock_path)?;
+        }
+    }
+
+    // Directories without lock file
+    for entry in fs::read_dir(path)? {
+        let dir = entry?.path();
+        if dir.is_dir() && !dir.with_extension("lock").exists() {
+            debug!("removing stale worktrees directory {}", dir.display());
+            fs::remove_dir_all(&dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the lock file at the path provided (creating it if needed) and lock
+/// it, waiting until it is available. The lock is released when the file
+/// returned is dropped.
+fn lock_file(path: &Path) -> Result<File, io::Error> {
+    let file = open_lock_file(path)?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+/// Like lock_file, but None is returned instead of waiting if the lock file
+/// is already locked.
+fn try_lock_file(path: &Path) -> Result<Option<File>, io::Error> {
+    let file = open_lock_file(path)?;
+    match file.try_lock_exclusive() {
+        Ok(()) => Ok(Some(file)),
+        Err(err) if err.kind() == fs2::lock_contended_error().kind() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Open the lock file at the path provided, creating it if needed.
+fn open_lock_file(path: &Path) -> Result<File, io::Error> {
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Return the size in bytes of the directory at the path provided. Entries
+/// removed while walking the directory (i.e. by another tracker's fetch) are
+/// ignored.
+fn dir_size(path: &Path) -> Result<u64, Error> {
+    let not_found_as_zero = |err: io::Error| match err.kind() {
+        io::ErrorKind::NotFound => Ok(0),
+        _ => Err(err),
+    };
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => return Ok(not_found_as_zero(err)?),
+    };
+    let mut size = 0;
+    for entry in entries {
+        let entry = entry?;
+        size += match entry.metadata() {
+            Ok(md) if md.is_dir() => dir_size(&entry.path())?,
+            Ok(md) => md.len(),
+            Err(err) => not_found_as_zero(err)?,
+        };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    /// Run the git command provided in the path given.
+    fn git(path: &Path, args: &[&str]) {
+        let status = process::Command::new("git")
+            .current_dir(path)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn mirror_name_from_url() {
+        assert_eq!(
+            mirror_name("https://github.com/cncf/clomonitor"),
+            "github.com_cncf_clomonitor.git"
+        );
+        assert_eq!(
+            mirror_name("https://gitlab.com/org/group/repo.git/"),
+            "gitlab.com_org_group_repo.git"
+        );
+    }
+
+    #[test]
+    fn dir_size_of_missing_dir_is_zero() {
+        assert_eq!(dir_size(Path::new("/nonexistent")).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn checkout_worktrees_and_evict_mirrors() {
+        let base = env::temp_dir().join(format!("clomonitor-mirror-{}", process::id()));
+        let src = base.join("src");
+        fs::create_dir_all(&src).unwrap();
+        git(&src, &["init", "-q", "-b", "main"]);
+        git(&src, &["config", "user.email", "test@example.com"]);
+        git(&src, &["config", "user.name", "test"]);
+        fs::write(src.join("README.md"), "initial").unwrap();
+        git(&src, &["add", "."]);
+        git(&src, &["commit", "-q", "-m", "initial"]);
+        let url = format!("file://{}", src.display());
+        let cache_dir = base.join("cache");
+
+        // Worktrees are removed when dropped
+        let cache = MirrorCache::new(&cache_dir, "a", u64::MAX, HOUR).unwrap();
+        let worktree = cache
+            .checkout(&url, &RemoteOptions::default())
+            .await
+            .unwrap();
+        let path = worktree.path().to_path_buf();
+        assert_eq!(
+            fs::read_to_string(path.join("README.md")).unwrap(),
+            "initial"
+        );
+        drop(worktree);
+        assert!(!path.exists());
+
+        // Mirrors are updated incrementally
+        fs::write(src.join("README.md"), "updated").unwrap();
+        git(&src, &["commit", "-q", "-am", "update"]);
+        let worktree = cache
+            .checkout(&url, &RemoteOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(worktree.path().join("README.md")).unwrap(),
+            "updated"
+        );
+
+        // Leftover worktrees are only removed once their tracker is gone
+        drop(worktree);
+        let path = cache_dir.join("worktrees").join("a").join("leftover");
+        fs::create_dir_all(&path).unwrap();
+        assert!(MirrorCache::new(&cache_dir, "a", u64::MAX, HOUR).is_err());
+        let cache_b = MirrorCache::new(&cache_dir, "b", u64::MAX, HOUR).unwrap();
+        assert!(path.exists());
+        drop(cache);
+        drop(cache_b);
+        let cache = MirrorCache::new(&cache_dir, "c", u64::MAX, HOUR).unwrap();
+        assert!(!path.exists());
+        assert!(!cache_dir.join("worktrees").join("a.lock").exists());
+        assert!(!cache_dir.join("worktrees").join("b").exists());
+
+        // Mirrors within limits are kept, the rest are evicted
+        let mirror = cache_dir.join("mirrors").join(mirror_name(&url));
+        cache.evict().unwrap();
+        assert!(mirror.exists());
+        let cache = MirrorCache::new(&cache_dir, "d", 1, HOUR).unwrap();
+        let worktree = cache
+            .checkout(&url, &RemoteOptions::default())
+            .await
+            .unwrap();
+        cache.evict().unwrap();
+        assert!(mirror.exists());
+        drop(worktree);
+        cache.evict().unwrap();
+        assert!(!mirror.exists());
+        drop(
+            cache
+                .checkout(&url, &RemoteOptions::default())
+                .await
+                .unwrap(),
+        );
+        let cache = MirrorCache::new(&cache_dir, "e", u64::MAX, Duration::ZERO).unwrap();
+        cache.evict().unwrap();
+        assert!(!mirror.exists());
+
+        fs::remove_dir_all(base).unwrap();
+    }
+}
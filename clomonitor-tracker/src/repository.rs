@@ -1,14 +1,14 @@
-use anyhow::{format_err, Error};
+use crate::mirror::MirrorCache;
+use anyhow::Error;
 use chrono::{DateTime, Duration, Utc};
 use clomonitor_core::{
-    linter::{lint, CheckSet, LintOptions, Report},
-    score::{self, Score},
+    git::{self, Credentials, RemoteOptions},
+    linter::{lint, CheckSet, LintOptions, Registry, Report, LINTER_VERSION, METADATA_FILE},
+    score::{self, Profile, Score},
 };
-use deadpool_postgres::{Client as DbClient, Transaction};
-use std::path::Path;
+use deadpool_postgres::{Client as DbClient, Pool, Transaction};
+use std::path::PathBuf;
 use std::time::Instant;
-use tempdir::TempDir;
-use tokio::process::Command;
 use tokio_postgres::types::Json;
 use tokio_postgres::Error as DbError;
 use tracing::{debug, warn};
@@ -22,6 +22,8 @@ pub(crate) struct Repository {
     check_sets: Vec<CheckSet>,
     digest: Option<String>,
     updated_at: DateTime<Utc>,
+    max_staleness: Duration,
+    scoring_profile: Option<String>,
 }
 
 impl Repository {
@@ -30,40 +32,75 @@ impl Repository {
         self.repository_id
     }
 
+    /// Get the name of the scoring profile selected for the repository's
+    /// project, if any.
+    pub(crate) fn scoring_profile(&self) -> Option<&str> {
+        self.scoring_profile.as_deref()
+    }
+
     /// Track repository if it has changed since the last time it was tracked.
-    /// This involves cloning the repository, linting it and storing the results.
+    /// This involves checking out the repository from its mirror, linting it
+    /// and storing the results. A database client is only taken from the pool
+    /// once the results are ready to be stored.
     pub(crate) async fn track(
         &self,
-        mut db: DbClient,
+        db_pool: &Pool,
+        cache: &MirrorCache,
         github_token: Option<String>,
+        gitlab_token: Option<String>,
+        custom_checks_path: Option<PathBuf>,
+        profile: Profile,
     ) -> Result<(), Error> {
         let start = Instant::now();
+        let remote_opts =
+            remote_options(&self.url, github_token.as_deref(), gitlab_token.as_deref());
 
         // Process only if the repository has changed since the last time it
-        // was tracked or if it hasn't been tracked in more than 1 day
-        let remote_digest = self.get_remote_digest().await?;
+        // was tracked or if it hasn't been tracked in longer than its maximum
+        // staleness
+        let remote_digest = git::remote_digest(&self.url, &remote_opts).await?;
         if let Some(digest) = &self.digest {
-            if &remote_digest == digest && self.updated_at > Utc::now() - Duration::days(1) {
+            if &remote_digest == digest && self.updated_at > Utc::now() - self.max_staleness {
                 return Ok(());
             }
         }
 
         debug!("tracking repository [id: {}]", self.repository_id);
 
-        // Clone repository
-        let tmp_dir = TempDir::new("clomonitor")?;
-        self.clone(tmp_dir.path()).await?;
+        // Check out repository (the worktree is removed when dropped)
+        let worktree = cache.checkout(&self.url, &remote_opts).await?;
 
         // Lint repository
         let mut errors: Option<String> = None;
+        let mut registry = Registry::default();
         let options = LintOptions {
             check_sets: self.check_sets.clone(),
-            root: tmp_dir.into_path(),
+            root: worktree.path().to_path_buf(),
             url: self.url.clone(),
+            offline: false,
             github_token,
+            gitlab_token,
         };
-        let report = match lint(options).await {
-            Ok(report) => Some(report),
+        let report = match self.lint(options, &mut registry, custom_checks_path).await {
+            Ok(report) => {
+                // Errors in individual checks don't prevent the report from
+                // being stored, but we keep track of them as well
+                let check_errors = report.errors(&registry);
+                if !check_errors.is_empty() {
+                    warn!(
+                        "some checks failed linting repository [id: {}]",
+                        self.repository_id
+                    );
+                    errors = Some(
+                        check_errors
+                            .iter()
+                            .map(|(check_id, err)| format!("{check_id}: {err}"))
+                            .collect::<Vec<String>>()
+                            .join("\n"),
+                    );
+                }
+                Some(report)
+            }
             Err(err) => {
                 warn!(
                     "error linting repository [id: {}]: {}",
@@ -76,10 +113,13 @@ impl Repository {
         };
 
         // Store tracking results in database
+        let mut db = db_pool.get().await?;
         let tx = db.transaction().await?;
-        self.store_report(&tx, &report, errors).await?;
-        self.update_score(&tx, &report).await?;
-        self.update_project_score(&tx).await?;
+        self.store_report(&tx, &report, errors, &remote_digest)
+            .await?;
+        self.update_score(&tx, &report, &registry, &profile, &remote_digest)
+            .await?;
+        self.update_project_score(&tx, &profile).await?;
         self.update_digest(&tx, &remote_digest).await?;
         tx.commit().await?;
 
@@ -91,42 +131,28 @@ impl Repository {
         Ok(())
     }
 
-    /// Get the remote digest of a repository.
-    async fn get_remote_digest(&self) -> Result<String, Error> {
-        let output = Command::new("git")
-            .arg("ls-remote")
-            .arg(&self.url)
-            .arg("HEAD")
-            .output()
-            .await?;
-        if !output.status.success() {
-            return Err(format_err!("{}", String::from_utf8_lossy(&output.stderr)));
+    /// Lint the repository using the built-in checks as well as the custom
+    /// ones declared in the custom checks file and the repository metadata.
+    async fn lint(
+        &self,
+        options: LintOptions,
+        registry: &mut Registry,
+        custom_checks_path: Option<PathBuf>,
+    ) -> Result<Report, Error> {
+        if let Some(path) = custom_checks_path {
+            registry.register_custom_checks(path)?;
         }
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(stdout.split_whitespace().next().unwrap().to_string())
+        registry.register_custom_checks(options.root.join(METADATA_FILE))?;
+        lint(options, registry).await
     }
 
-    /// Clone (shallow) the source git repo in the destination path provided.
-    async fn clone(&self, dst: &Path) -> Result<(), Error> {
-        let output = Command::new("git")
-            .arg("clone")
-            .arg("--depth=10")
-            .arg(&self.url)
-            .arg(dst)
-            .output()
-            .await?;
-        if !output.status.success() {
-            return Err(format_err!("{}", String::from_utf8_lossy(&output.stderr)));
-        }
-        Ok(())
-    }
-
-    /// Store the provided linter report.
+    /// Store the provided linter report, keeping a snapshot of it as well.
     async fn store_report(
         &self,
         tx: &Transaction<'_>,
         report: &Option<Report>,
         errors: Option<String>,
+        digest: &str,
     ) -> Result<(), Error> {
         match report {
             Some(report) => {
@@ -159,26 +185,69 @@ impl Repository {
                 .await?;
             }
         }
+        tx.execute(
+            "
+            insert into report_snapshot (digest, linter_version, data, errors, repository_id)
+            values ($1::text, $2::text, $3::jsonb, $4::text, $5::uuid);
+            ",
+            &[
+                &digest,
+                &LINTER_VERSION,
+                &report.as_ref().map(Json),
+                &errors,
+                &self.repository_id,
+            ],
+        )
+        .await?;
 
         Ok(())
     }
 
-    /// Update repository's score based on the provided linter report.
+    /// Update repository's score (and its explanation) based on the provided
+    /// linter report, keeping a snapshot of the score as well.
     async fn update_score(
         &self,
         tx: &Transaction<'_>,
         report: &Option<Report>,
+        registry: &Registry,
+        profile: &Profile,
+        digest: &str,
     ) -> Result<(), Error> {
         if let Some(report) = report {
-            let score = score::calculate(report);
+            let score = score::calculate(report, registry, profile);
+            let explanation = score::explain(report, registry, profile);
             tx.execute(
                 "
                 update repository set
                     score = $1::jsonb,
+                    score_explanation = $2::jsonb,
                     updated_at = current_timestamp
-                where repository_id = $2::uuid;
+                where repository_id = $3::uuid;
                 ",
-                &[&Json(&score), &self.repository_id],
+                &[&Json(&score), &Json(&explanation), &self.repository_id],
+            )
+            .await?;
+            tx.execute(
+                "
+                insert into score_snapshot (
+                    digest,
+                    linter_version,
+                    score,
+                    rating,
+                    project_id,
+                    repository_id
+                )
+                select $1::text, $2::text, $3::jsonb, $4::text, project_id, repository_id
+                from repository
+                where repository_id = $5::uuid;
+                ",
+                &[
+                    &digest,
+                    &LINTER_VERSION,
+                    &Json(&score),
+                    &profile.rating(score.global()).to_string(),
+                    &self.repository_id,
+                ],
             )
             .await?;
         }
@@ -186,8 +255,13 @@ impl Repository {
         Ok(())
     }
 
-    /// Update project's score based on the project's repositories scores.
-    async fn update_project_score(&self, tx: &Transaction<'_>) -> Result<(), Error> {
+    /// Update project's score based on the project's repositories scores,
+    /// keeping a snapshot of it as well.
+    async fn update_project_score(
+        &self,
+        tx: &Transaction<'_>,
+        profile: &Profile,
+    ) -> Result<(), Error> {
         // Get project's id and lock project's row
         let row = tx
             .query_one(
@@ -225,6 +299,7 @@ impl Repository {
         // Update project's score and rating
         if !repositories_scores.is_empty() {
             let project_score = score::merge(repositories_scores);
+            let rating = profile.rating(project_score.global()).to_string();
             tx.execute(
                 "
             update project set
@@ -233,11 +308,15 @@ impl Repository {
                 updated_at = current_timestamp
             where project_id = $3::uuid;
             ",
-                &[
-                    &Json(&project_score),
-                    &project_score.rating().to_string(),
-                    &project_id,
-                ],
+                &[&Json(&project_score), &rating, &project_id],
+            )
+            .await?;
+            tx.execute(
+                "
+                insert into score_snapshot (linter_version, score, rating, project_id)
+                values ($1::text, $2::jsonb, $3::text, $4::uuid);
+                ",
+                &[&LINTER_VERSION, &Json(&project_score), &rating, &project_id],
             )
             .await?;
         }
@@ -256,33 +335,57 @@ impl Repository {
     }
 }
 
-/// Get all repositories available in the database.
-pub(crate) async fn get_all(db: DbClient) -> Result<Vec<Repository>, DbError> {
-    debug!("getting repositories");
-    let mut repositories: Vec<Repository> = Vec::new();
-    let rows = db
-        .query(
+/// Prepare the options used to access the remote repository at the url
+/// provided, using the token of its git hosting provider as credentials (they
+/// are only sent if the remote requires authentication).
+fn remote_options(
+    url: &str,
+    github_token: Option<&str>,
+    gitlab_token: Option<&str>,
+) -> RemoteOptions {
+    RemoteOptions {
+        credentials: Credentials::from_token(url, github_token, gitlab_token),
+        ..Default::default()
+    }
+}
+
+/// Get the repository with the id provided, if it exists. The maximum
+/// staleness provided is used if the repository doesn't set its own.
+pub(crate) async fn get(
+    db: &DbClient,
+    repository_id: Uuid,
+    default_max_staleness: Duration,
+) -> Result<Option<Repository>, DbError> {
+    debug!("getting repository [id: {repository_id}]");
+    let row = db
+        .query_opt(
             "
             select
-                repository_id,
-                url,
-                digest,
-                to_json(check_sets) as check_sets,
-                updated_at
-            from repository
+                r.repository_id,
+                r.url,
+                r.digest,
+                to_json(r.check_sets) as check_sets,
+                r.updated_at,
+                floor(extract(epoch from r.max_staleness))::bigint as max_staleness,
+                p.scoring_profile
+            from repository r
+            join project p using (project_id)
+            where r.repository_id = $1::uuid
             ",
-            &[],
+            &[&repository_id],
         )
         .await?;
-    for row in rows {
+    Ok(row.map(|row| {
         let Json(check_sets): Json<Vec<CheckSet>> = row.get("check_sets");
-        repositories.push(Repository {
+        let max_staleness: Option<i64> = row.get("max_staleness");
+        Repository {
             repository_id: row.get("repository_id"),
             url: row.get("url"),
             check_sets,
             digest: row.get("digest"),
             updated_at: row.get("updated_at"),
-        });
-    }
-    Ok(repositories)
+            max_staleness: max_staleness.map_or(default_max_staleness, Duration::seconds),
+            scoring_profile: row.get("scoring_profile"),
+        }
+    }))
 }
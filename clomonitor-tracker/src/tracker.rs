@@ -1,52 +1,350 @@
-use crate::repository;
+use crate::{
+    job::{self, Job},
+    mirror::MirrorCache,
+    repository,
+};
 use anyhow::Error;
+use chrono::Duration as ChronoDuration;
+use clomonitor_core::score::Profile;
 use config::Config;
-use deadpool_postgres::Pool;
+use deadpool_postgres::{Client as DbClient, Pool};
 use futures::{
     future,
     stream::{FuturesUnordered, StreamExt},
 };
-use std::time::Duration;
-use tokio::time::timeout;
-use tracing::{error, info};
+use std::{env, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    signal,
+    sync::watch,
+    time::{sleep, timeout},
+};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
 /// Maximum time that can take tracking a single repository.
 const REPOSITORY_TRACK_TIMEOUT: u64 = 300;
 
+/// Time between checks for jobs due in daemon mode.
+const DAEMON_POLL_INTERVAL: u64 = 60;
+
 /// Track all repositories registered in the database.
 pub(crate) async fn run(cfg: Config, db_pool: Pool) -> Result<(), Error> {
     info!("tracker started");
+    let shutdown = shutdown_signal();
+    let tracker = Arc::new(Tracker::new(cfg, db_pool)?);
 
-    // Get repositories to process
-    let repositories = repository::get_all(db_pool.get().await?).await?;
-    if repositories.is_empty() {
+    // Make the jobs of all repositories due
+    if job::enqueue(&tracker.db_pool.get().await?, true).await? == 0 {
         info!("no repositories found");
         info!("tracker finished");
         return Ok(());
     }
 
     // Track repositories
-    info!("tracking repositories");
-    let mut futs = FuturesUnordered::new();
-    for repository in repositories {
-        let db = db_pool.get().await?;
-        let github_token = cfg.get_str("creds.githubToken").ok();
-        futs.push(tokio::spawn(async move {
-            if let Err(err) = timeout(
-                Duration::from_secs(REPOSITORY_TRACK_TIMEOUT),
-                repository.track(db, github_token),
-            )
-            .await
-            {
-                error!("error tracking repository {}: {err}", repository.id());
-            }
-        }));
-        if futs.len() == cfg.get::<usize>("tracker.concurrency").unwrap() {
-            futs.next().await;
+    tracker.work(&shutdown).await?;
+
+    info!("tracker finished");
+    Ok(())
+}
+
+/// Track the repositories registered in the database periodically, until a
+/// shutdown signal is received.
+pub(crate) async fn run_daemon(cfg: Config, db_pool: Pool) -> Result<(), Error> {
+    info!("tracker started in daemon mode");
+    let mut shutdown = shutdown_signal();
+    let tracker = Arc::new(Tracker::new(cfg, db_pool)?);
+
+    loop {
+        // Create the jobs of the repositories added since the last check
+        match tracker.db_pool.get().await {
+            Ok(db) => {
+                if let Err(err) = job::enqueue(&db, false).await {
+                    error!("error enqueuing jobs: {err}");
+                }
+            }
+            Err(err) => error!("error getting db client: {err}"),
+        }
+
+        // Track the repositories due
+        if let Err(err) = tracker.work(&shutdown).await {
+            error!("error tracking repositories: {err}");
+        }
+
+        // Wait until the next check, unless we've been asked to stop
+        if *shutdown.borrow() {
+            break;
+        }
+        tokio::select! {
+            _ = sleep(Duration::from_secs(DAEMON_POLL_INTERVAL)) => {},
+            _ = shutdown.changed() => break,
         }
     }
-    future::join_all(futs).await;
 
-    info!("tracker finished");
+    info!("tracker stopped");
+    Ok(())
+}
+
+/// Tracker in charge of linting and scoring the repositories whose tracking
+/// jobs are due. Multiple trackers can share the work safely, as each job is
+/// claimed by a single tracker at a time.
+struct Tracker {
+    cfg: Config,
+    db_pool: Pool,
+    worker_id: String,
+    profiles: Vec<Profile>,
+    cache: MirrorCache,
+    concurrency: usize,
+    max_staleness: ChronoDuration,
+    interval: Duration,
+    jitter: Duration,
+    lease: Duration,
+    max_attempts: i32,
+    retry_delay: Duration,
+}
+
+impl Tracker {
+    /// Create a new tracker, loading the scoring profiles available and
+    /// setting up the repositories mirror cache.
+    fn new(cfg: Config, db_pool: Pool) -> Result<Self, Error> {
+        // Load scoring profiles available
+        let profiles = match cfg.get_str("tracker.scoringProfilesPath") {
+            Ok(path) => Profile::from_file_list(path)?,
+            Err(_) => vec![],
+        };
+
+        // Identify this tracker in the jobs it claims and the cache
+        let hostname = env::var("HOSTNAME").unwrap_or_else(|_| "tracker".to_string());
+        let worker_id = format!("{hostname}-{}", Uuid::new_v4());
+
+        // Setup repositories mirror cache
+        let cache = MirrorCache::new(
+            &PathBuf::from(cfg.get_str("tracker.cache.path")?),
+            &worker_id,
+            cfg.get::<u64>("tracker.cache.maxSizeMb")? * 1024 * 1024,
+            Duration::from_secs(cfg.get::<u64>("tracker.cache.maxAgeDays")? * 24 * 60 * 60),
+        )?;
+
+        // Make sure the database pool is large enough for the concurrency
+        // configured: each job in progress may use a client to run its queries
+        // and another one to renew its lease, and we need one more to claim
+        // new jobs
+        let concurrency: usize = cfg.get("tracker.concurrency")?;
+        let min_pool_size = 2 * concurrency + 1;
+        if db_pool.status().max_size < min_pool_size {
+            debug!("resizing database pool to {min_pool_size} clients");
+            db_pool.resize(min_pool_size);
+        }
+
+        Ok(Self {
+            concurrency,
+            max_staleness: ChronoDuration::hours(cfg.get("tracker.maxStalenessHours")?),
+            interval: Duration::from_secs(cfg.get::<u64>("tracker.daemon.intervalMinutes")? * 60),
+            jitter: Duration::from_secs(cfg.get::<u64>("tracker.daemon.jitterMinutes")? * 60),
+            lease: Duration::from_secs(cfg.get("tracker.jobs.leaseSeconds")?),
+            max_attempts: cfg.get("tracker.jobs.maxAttempts")?,
+            retry_delay: Duration::from_secs(cfg.get("tracker.jobs.retryDelaySeconds")?),
+            cfg,
+            db_pool,
+            worker_id,
+            profiles,
+            cache,
+        })
+    }
+
+    /// Claim and run the jobs due until there are none left or a shutdown
+    /// signal is received. In the latter case, the jobs in progress are given
+    /// the chance to finish.
+    async fn work(self: &Arc<Self>, shutdown: &watch::Receiver<bool>) -> Result<(), Error> {
+        let mut jobs_run = 0;
+        let mut futs = FuturesUnordered::new();
+        loop {
+            if *shutdown.borrow() {
+                info!("waiting for the repositories in progress to finish");
+                break;
+            }
+            if futs.len() == self.concurrency {
+                futs.next().await;
+                continue;
+            }
+            let db = self.db_pool.get().await?;
+            let job = match job::claim(&db, &self.worker_id, self.lease).await? {
+                Some(job) => job,
+                None => break,
+            };
+            drop(db);
+            jobs_run += 1;
+            let tracker = self.clone();
+            futs.push(tokio::spawn(async move { tracker.run_job(job).await }));
+        }
+        future::join_all(futs).await;
+        if jobs_run == 0 {
+            return Ok(());
+        }
+        debug!("{jobs_run} jobs run");
+
+        // Evict mirrors not used recently or that don't fit in the cache
+        if let Err(err) = self.cache.evict() {
+            error!("error evicting mirrors: {err}");
+        }
+
+        // Remove snapshots older than the retention period
+        let retention_days = self.cfg.get::<i32>("tracker.snapshots.retentionDays")?;
+        if retention_days > 0 {
+            if let Err(err) = prune_snapshots(self.db_pool.get().await?, retention_days).await {
+                error!("error pruning snapshots: {err}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Track the repository of the job provided, renewing the job's lease
+    /// while doing it, and release the job once done. Failed jobs are retried
+    /// (with an exponential backoff) until they reach the maximum attempts.
+    async fn run_job(&self, job: Job) {
+        let heartbeat = tokio::spawn(job.clone().heartbeat(self.db_pool.clone(), self.lease));
+        let result = self.track(&job).await;
+        heartbeat.abort();
+
+        // Release job
+        let db = match self.db_pool.get().await {
+            Ok(db) => db,
+            Err(err) => {
+                error!("error getting db client: {err}");
+                return;
+            }
+        };
+        let result = match result {
+            Ok(()) => {
+                let next_run = job::next_run_delay(self.interval, self.jitter);
+                job.complete(&db, next_run).await
+            }
+            Err(err) => {
+                error!("error tracking repository {}: {err}", job.repository_id());
+                let retry = job.attempts() < self.max_attempts;
+                let next_run = if retry {
+                    job::retry_delay(self.retry_delay, job.attempts())
+                } else {
+                    warn!(
+                        "giving up tracking repository {} after {} attempts",
+                        job.repository_id(),
+                        job.attempts()
+                    );
+                    job::next_run_delay(self.interval, self.jitter)
+                };
+                job.fail(&db, &err.to_string(), next_run, retry).await
+            }
+        };
+        if let Err(err) = result {
+            error!(
+                "error releasing job [repository id: {}]: {err}",
+                job.repository_id()
+            );
+        }
+    }
+
+    /// Track the repository of the job provided.
+    async fn track(&self, job: &Job) -> Result<(), Error> {
+        let db = self.db_pool.get().await?;
+        let repository = match repository::get(&db, job.repository_id(), self.max_staleness).await?
+        {
+            Some(repository) => repository,
+            None => return Ok(()),
+        };
+        drop(db);
+        let github_token = self.cfg.get_str("creds.githubToken").ok();
+        let gitlab_token = self.cfg.get_str("creds.gitlabToken").ok();
+        let custom_checks_path = self
+            .cfg
+            .get_str("tracker.customChecksPath")
+            .ok()
+            .map(PathBuf::from);
+        let profile = select_profile(&self.profiles, repository.scoring_profile());
+        if let Some(name) = repository.scoring_profile() {
+            if profile.name != name {
+                warn!(
+                    "scoring profile {name} not found, using default [repository id: {}]",
+                    repository.id()
+                );
+            }
+        }
+        timeout(
+            Duration::from_secs(REPOSITORY_TRACK_TIMEOUT),
+            repository.track(
+                &self.db_pool,
+                &self.cache,
+                github_token,
+                gitlab_token,
+                custom_checks_path,
+                profile,
+            ),
+        )
+        .await?
+    }
+}
+
+/// Remove the report and score snapshots older than the number of days
+/// provided.
+async fn prune_snapshots(db: DbClient, retention_days: i32) -> Result<(), Error> {
+    let reports = db
+        .execute(
+            "
+            delete from report_snapshot
+            where created_at < current_timestamp - make_interval(days => $1::integer);
+            ",
+            &[&retention_days],
+        )
+        .await?;
+    let scores = db
+        .execute(
+            "
+            delete from score_snapshot
+            where created_at < current_timestamp - make_interval(days => $1::integer);
+            ",
+            &[&retention_days],
+        )
+        .await?;
+    debug!("pruned {reports} report and {scores} score snapshots");
     Ok(())
 }
+
+/// Select the scoring profile with the name provided from the list of
+/// profiles available, falling back to the default profile when not found.
+fn select_profile(profiles: &[Profile], name: Option<&str>) -> Profile {
+    name.and_then(|name| profiles.iter().find(|p| p.name == name))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Return a receiver that is notified when a shutdown signal is received.
+fn shutdown_signal() -> watch::Receiver<bool> {
+    let (tx, rx) = watch::channel(false);
+    tokio::spawn(async move {
+        // Setup signal handlers
+        let ctrl_c = async {
+            signal::ctrl_c()
+                .await
+                .expect("failed to install ctrl+c signal handler");
+        };
+
+        #[cfg(unix)]
+        let terminate = async {
+            signal::unix::signal(signal::unix::SignalKind::terminate())
+                .expect("failed to install terminate signal handler")
+                .recv()
+                .await;
+        };
+
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        // Wait for any of the signals
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = terminate => {},
+        }
+        info!("tracker stopping...");
+        let _ = tx.send(true);
+    });
+    rx
+}
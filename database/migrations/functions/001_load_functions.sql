@@ -1,4 +1,6 @@
 {{ template "projects/get_project.sql" }}
+{{ template "projects/get_project_score_series.sql" }}
+{{ template "projects/get_repository_score_series.sql" }}
 {{ template "projects/search_projects.sql" }}
 {{ template "stats/average_section_score.sql" }}
 {{ template "stats/repositories_passing_check.sql" }}
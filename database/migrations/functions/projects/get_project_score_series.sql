@@ -0,0 +1,17 @@
+create or replace function get_project_score_series(p_org_name text, p_project_name text)
+returns json as $$
+    select (
+        select coalesce(json_agg(json_build_object(
+            'score', s.score,
+            'rating', s.rating,
+            'linter_version', s.linter_version,
+            'created_at', floor(extract(epoch from s.created_at))
+        ) order by s.created_at asc), '[]')
+        from score_snapshot s
+        where s.project_id = p.project_id
+        and s.repository_id is null
+    )
+    from project p
+    join organization o using (organization_id)
+    where o.name = p_org_name and p.name = p_project_name;
+$$ language sql;
@@ -0,0 +1,24 @@
+create or replace function get_repository_score_series(
+    p_org_name text,
+    p_project_name text,
+    p_repository_name text
+)
+returns json as $$
+    select (
+        select coalesce(json_agg(json_build_object(
+            'score', s.score,
+            'rating', s.rating,
+            'digest', s.digest,
+            'linter_version', s.linter_version,
+            'created_at', floor(extract(epoch from s.created_at))
+        ) order by s.created_at asc), '[]')
+        from score_snapshot s
+        where s.repository_id = r.repository_id
+    )
+    from repository r
+    join project p using (project_id)
+    join organization o using (organization_id)
+    where o.name = p_org_name
+    and p.name = p_project_name
+    and r.name = p_repository_name;
+$$ language sql;
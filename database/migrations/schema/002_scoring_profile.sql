@@ -0,0 +1 @@
+alter table project add column scoring_profile text check (scoring_profile <> '');
@@ -0,0 +1 @@
+alter table repository add column score_explanation jsonb;
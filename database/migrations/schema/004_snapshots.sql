@@ -0,0 +1,27 @@
+create table if not exists report_snapshot (
+    report_snapshot_id uuid primary key default gen_random_uuid(),
+    digest text not null check (digest <> ''),
+    linter_version text not null check (linter_version <> ''),
+    data jsonb,
+    errors text,
+    created_at timestamptz default current_timestamp not null,
+    repository_id uuid not null references repository on delete cascade
+);
+
+create index report_snapshot_repository_id_created_at_idx on report_snapshot (repository_id, created_at);
+create index report_snapshot_created_at_idx on report_snapshot (created_at);
+
+create table if not exists score_snapshot (
+    score_snapshot_id uuid primary key default gen_random_uuid(),
+    digest text check (digest <> ''),
+    linter_version text not null check (linter_version <> ''),
+    score jsonb not null,
+    rating text,
+    created_at timestamptz default current_timestamp not null,
+    project_id uuid not null references project on delete cascade,
+    repository_id uuid references repository on delete cascade
+);
+
+create index score_snapshot_project_id_created_at_idx on score_snapshot (project_id, created_at);
+create index score_snapshot_repository_id_created_at_idx on score_snapshot (repository_id, created_at);
+create index score_snapshot_created_at_idx on score_snapshot (created_at);
@@ -0,0 +1 @@
+alter table repository add column max_staleness interval check (max_staleness > '0'::interval);
@@ -0,0 +1,14 @@
+create table if not exists tracking_job (
+    tracking_job_id uuid primary key default gen_random_uuid(),
+    run_at timestamptz default current_timestamp not null,
+    attempts integer default 0 not null,
+    locked_by text check (locked_by <> ''),
+    locked_until timestamptz,
+    heartbeat_at timestamptz,
+    last_error text,
+    created_at timestamptz default current_timestamp not null,
+    repository_id uuid not null references repository on delete cascade,
+    unique (repository_id)
+);
+
+create index tracking_job_run_at_idx on tracking_job (run_at);
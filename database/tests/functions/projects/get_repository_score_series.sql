@@ -0,0 +1,116 @@
+-- Start transaction and plan tests
+begin;
+select plan(3);
+
+-- Non existing repository
+select is(
+    get_repository_score_series('non-existing', 'non-existing', 'non-existing')::jsonb,
+    (null::jsonb),
+    'Null is returned if the requested repository does not exist'
+);
+
+-- Seed some data
+insert into organization (
+    organization_id,
+    name
+) values (
+    '00000001-0000-0000-0000-000000000000',
+    'artifact-hub'
+);
+insert into project (
+    project_id,
+    name,
+    organization_id,
+    category_id,
+    maturity_id
+) values (
+    '00000000-0001-0000-0000-000000000000',
+    'artifact-hub',
+    '00000001-0000-0000-0000-000000000000',
+    0,
+    2
+);
+insert into repository (
+    repository_id,
+    name,
+    url,
+    check_sets,
+    project_id
+) values (
+    '00000000-0000-0001-0000-000000000000',
+    'artifact-hub',
+    'https://github.com/artifacthub/hub',
+    '{code, community}',
+    '00000000-0001-0000-0000-000000000000'
+);
+
+-- Repository without snapshots
+select is(
+    get_repository_score_series('artifact-hub', 'artifact-hub', 'artifact-hub')::jsonb,
+    '[]'::jsonb,
+    'Empty series returned if the repository has no score snapshots'
+);
+
+-- Seed some snapshots
+insert into score_snapshot (
+    digest,
+    linter_version,
+    score,
+    rating,
+    created_at,
+    project_id,
+    repository_id
+) values (
+    'c2a7bbbb4ba4b9c0bb2e4b2c4a1b1f2f0ee2a1a5',
+    '0.5.0',
+    '{"global": 80}',
+    'a',
+    '2022-02-25 09:40:42.695654+01',
+    '00000000-0001-0000-0000-000000000000',
+    '00000000-0000-0001-0000-000000000000'
+), (
+    '653b5219d16a2e5be274a7fb765916789ae68fbb',
+    '0.4.0',
+    '{"global": 70}',
+    'b',
+    '2022-02-24 09:40:42.695654+01',
+    '00000000-0001-0000-0000-000000000000',
+    '00000000-0000-0001-0000-000000000000'
+);
+insert into score_snapshot (
+    linter_version,
+    score,
+    rating,
+    project_id
+) values (
+    '0.5.0',
+    '{"global": 80}',
+    'a',
+    '00000000-0001-0000-0000-000000000000'
+);
+
+-- Run some tests
+select is(
+    get_repository_score_series('artifact-hub', 'artifact-hub', 'artifact-hub')::jsonb,
+    '[
+        {
+            "score": {"global": 70},
+            "rating": "b",
+            "digest": "653b5219d16a2e5be274a7fb765916789ae68fbb",
+            "linter_version": "0.4.0",
+            "created_at": 1645692042
+        },
+        {
+            "score": {"global": 80},
+            "rating": "a",
+            "digest": "c2a7bbbb4ba4b9c0bb2e4b2c4a1b1f2f0ee2a1a5",
+            "linter_version": "0.5.0",
+            "created_at": 1645778442
+        }
+    ]'::jsonb,
+    'Repository score snapshots returned as a json array sorted by date'
+);
+
+-- Finish tests and rollback transaction
+select * from finish();
+rollback;
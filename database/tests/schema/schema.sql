@@ -1,6 +1,6 @@
 -- Start transaction and plan tests
 begin;
-select plan(25);
+select plan(36);
 
 -- Check expected extension exist
 select has_extension('pgcrypto');
@@ -11,7 +11,10 @@ select has_table('maturity');
 select has_table('organization');
 select has_table('project');
 select has_table('report');
+select has_table('report_snapshot');
 select has_table('repository');
+select has_table('score_snapshot');
+select has_table('tracking_job');
 
 -- Check tables have expected columns
 select columns_are('category', array[
@@ -41,6 +44,7 @@ select columns_are('project', array[
     'devstats_url',
     'score',
     'rating',
+    'scoring_profile',
     'accepted_at',
     'created_at',
     'updated_at',
@@ -56,6 +60,15 @@ select columns_are('report', array[
     'updated_at',
     'repository_id'
 ]);
+select columns_are('report_snapshot', array[
+    'report_snapshot_id',
+    'digest',
+    'linter_version',
+    'data',
+    'errors',
+    'created_at',
+    'repository_id'
+]);
 select columns_are('repository', array[
     'repository_id',
     'name',
@@ -63,10 +76,33 @@ select columns_are('repository', array[
     'check_sets',
     'digest',
     'score',
+    'score_explanation',
+    'max_staleness',
     'created_at',
     'updated_at',
     'project_id'
 ]);
+select columns_are('score_snapshot', array[
+    'score_snapshot_id',
+    'digest',
+    'linter_version',
+    'score',
+    'rating',
+    'created_at',
+    'project_id',
+    'repository_id'
+]);
+select columns_are('tracking_job', array[
+    'tracking_job_id',
+    'run_at',
+    'attempts',
+    'locked_by',
+    'locked_until',
+    'heartbeat_at',
+    'last_error',
+    'created_at',
+    'repository_id'
+]);
 
 -- Check tables have expected indexes
 select indexes_are('category', array[
@@ -89,14 +125,32 @@ select indexes_are('report', array[
     'report_pkey',
     'report_repository_id_key'
 ]);
+select indexes_are('report_snapshot', array[
+    'report_snapshot_pkey',
+    'report_snapshot_repository_id_created_at_idx',
+    'report_snapshot_created_at_idx'
+]);
 select indexes_are('repository', array[
     'repository_pkey',
     'repository_project_id_name_key'
 ]);
+select indexes_are('score_snapshot', array[
+    'score_snapshot_pkey',
+    'score_snapshot_project_id_created_at_idx',
+    'score_snapshot_repository_id_created_at_idx',
+    'score_snapshot_created_at_idx'
+]);
+select indexes_are('tracking_job', array[
+    'tracking_job_pkey',
+    'tracking_job_run_at_idx',
+    'tracking_job_repository_id_key'
+]);
 
 -- Check expected functions exist
 -- Projects
 select has_function('get_project');
+select has_function('get_project_score_series');
+select has_function('get_repository_score_series');
 select has_function('search_projects');
 select has_function('repositories_passing_check');
 select has_function('get_stats');
@@ -149,5 +149,38 @@ describe('OptionCell', () => {
         jest.useRealTimers();
       });
     });
+
+    describe('error', () => {
+      it('displays error tooltip', async () => {
+        jest.useFakeTimers();
+
+        render(
+          <table>
+            <tbody>
+              <OptionCell
+                label={ReportOption.TrademarkDisclaimer}
+                check={{
+                  passed: false,
+                  exempt: false,
+                  error: 'error sending request',
+                }}
+              />
+            </tbody>
+          </table>
+        );
+
+        expect(screen.getAllByTestId('check-error-icon')).toHaveLength(2);
+        expect(screen.queryByTestId('error-icon')).toBeNull();
+
+        const icon = screen.getByTestId('elementWithTooltip');
+        userEvent.hover(icon);
+
+        expect(await screen.findByRole('tooltip')).toBeInTheDocument();
+        expect(screen.getByText('Something went wrong running this check, so it has not been scored')).toBeInTheDocument();
+        expect(screen.getByText(/error sending request/)).toBeInTheDocument();
+
+        jest.useRealTimers();
+      });
+    });
   });
 });
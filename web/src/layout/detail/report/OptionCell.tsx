@@ -22,6 +22,9 @@ const OptionCell = (props: Props) => {
   const errorIcon = <FaRegTimesCircle data-testid="error-icon" className={`text-danger ${styles.icon}`} />;
   const successIcon = <FaRegCheckCircle data-testid="success-icon" className={`text-success ${styles.icon}`} />;
   const exemptIcon = <RiErrorWarningLine data-testid="exempt-icon" className={`text-warning ${styles.exemptIcon}`} />;
+  const checkErrorIcon = (
+    <RiErrorWarningLine data-testid="check-error-icon" className={`text-muted ${styles.exemptIcon}`} />
+  );
 
   const opt: ReportOptionData = getOptionInfo(props.label);
 
@@ -36,7 +39,33 @@ const OptionCell = (props: Props) => {
   };
 
   const getIconCheck = (): JSX.Element => {
-    if (!isUndefined(props.check.exempt) && props.check.exempt) {
+    if (!isUndefined(props.check.error) && props.check.error !== '') {
+      return (
+        <>
+          <ElementWithTooltip
+            element={checkErrorIcon}
+            tooltipWidth={500}
+            className="cursorPointer"
+            tooltipClassName={styles.exemptionTooltipMessage}
+            tooltipMessage={
+              <div className="text-start p-2">
+                <div className="border-bottom pb-2 mb-3 fw-bold">
+                  Something went wrong running this check, so it has not been scored
+                </div>
+                <div className={`text-break ${styles.exemptionReason}`}>
+                  <span className="fw-bold">Error:</span> {props.check.error}
+                </div>
+              </div>
+            }
+            alignmentTooltip="left"
+            forceAlignment
+            visibleTooltip
+            active
+          />
+          <span className="d-block d-md-none">{checkErrorIcon}</span>
+        </>
+      );
+    } else if (!isUndefined(props.check.exempt) && props.check.exempt) {
       return (
         <>
           {!isUndefined(props.check.exemption_reason) && props.check.exemption_reason !== '' ? (
@@ -65,6 +65,25 @@ export interface ReportCheck {
   exemption_reason?: string;
   value?: string;
   url?: string;
+  error?: string;
+  details?: ReportCheckDetails;
+  evidence?: ReportCheckEvidence;
+}
+
+export interface ReportCheckEvidence {
+  file: string;
+  line: number;
+  snippet: string;
+  url: string;
+}
+
+export interface ReportCheckDetails {
+  globs?: string[];
+  readme_regexps?: string[];
+  community_repo_checked?: boolean;
+  sources?: string[];
+  remediation?: string;
+  docs_url?: string;
 }
 
 export interface FiltersSection {